@@ -1,15 +1,15 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse);
 
-pub fn part_one(input: &str) -> Option<i32> {
-    let mut lefts: Vec<i32> = Vec::new();
-    let mut rights: Vec<i32> = Vec::new();
+pub fn parse(input: &str) -> (Vec<u32>, Vec<u32>) {
+    let mut lefts: Vec<u32> = Vec::new();
+    let mut rights: Vec<u32> = Vec::new();
 
     input.lines().for_each(|line| {
-        let numbers: Vec<i32> = line
+        let numbers: Vec<u32> = line
             .split_whitespace()
-            .map(|num| num.parse::<i32>().expect("Invalid number"))
+            .map(|num| num.parse::<u32>().expect("Invalid number"))
             .collect();
 
         if numbers.len() == 2 {
@@ -18,6 +18,13 @@ pub fn part_one(input: &str) -> Option<i32> {
         }
     });
 
+    (lefts, rights)
+}
+
+pub fn part_one((lefts, rights): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
+    let mut lefts = lefts.clone();
+    let mut rights = rights.clone();
+
     lefts.sort_unstable();
     rights.sort_unstable();
 
@@ -25,34 +32,19 @@ pub fn part_one(input: &str) -> Option<i32> {
         lefts
             .iter()
             .zip(rights.iter())
-            .map(|(a, b)| (a - b).abs())
+            .map(|(a, b)| a.abs_diff(*b))
             .sum(),
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut lefts: Vec<u32> = Vec::new();
-    let mut rights: Vec<u32> = Vec::new();
-
-    input.lines().for_each(|line| {
-        let numbers: Vec<u32> = line
-            .split_whitespace()
-            .map(|num| num.parse::<u32>().expect("Invalid number"))
-            .collect();
-
-        if numbers.len() == 2 {
-            lefts.push(numbers[0]);
-            rights.push(numbers[1]);
-        }
-    });
-
+pub fn part_two((lefts, rights): &(Vec<u32>, Vec<u32>)) -> Option<u32> {
     let mut counter_map = HashMap::new();
 
     for right in rights {
-        let value = counter_map.get(&right);
+        let value = counter_map.get(right);
         match value {
-            Some(v) => counter_map.insert(right, v + 1),
-            None => counter_map.insert(right, 1),
+            Some(v) => counter_map.insert(*right, v + 1),
+            None => counter_map.insert(*right, 1),
         };
     }
 
@@ -68,15 +60,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
-    }
+    advent_of_code::examples!(parse; example);
 }
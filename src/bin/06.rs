@@ -1,9 +1,9 @@
 use std::collections::HashSet;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse);
 
 #[derive(Debug, PartialEq)]
-enum Cell {
+pub enum Cell {
     Obstacle,
     Empty,
 }
@@ -29,7 +29,7 @@ impl Direction {
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
-struct Position {
+pub struct Position {
     col: i32,
     row: i32,
 }
@@ -56,7 +56,7 @@ impl Guard {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn parse(input: &str) -> (Vec<Vec<Cell>>, Option<Position>) {
     let mut guard_position = None;
 
     let grid: Vec<Vec<Cell>> = input
@@ -80,8 +80,12 @@ pub fn part_one(input: &str) -> Option<u32> {
         })
         .collect();
 
+    (grid, guard_position)
+}
+
+pub fn part_one((grid, guard_position): &(Vec<Vec<Cell>>, Option<Position>)) -> Option<u32> {
     let mut guard = Guard {
-        position: guard_position?,
+        position: (*guard_position)?,
         direction: Direction::Up,
     };
     let mut visited = HashSet::new();
@@ -111,30 +115,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(visited.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut initial_guard_position = None;
-
-    let grid: Vec<Vec<Cell>> = input
-        .lines()
-        .enumerate()
-        .map(|(row_index, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(col_index, char)| match char {
-                    '^' => {
-                        initial_guard_position = Some(Position {
-                            col: col_index as i32,
-                            row: row_index as i32,
-                        });
-                        Cell::Empty
-                    }
-                    '#' => Cell::Obstacle,
-                    _ => Cell::Empty,
-                })
-                .collect()
-        })
-        .collect();
-
+pub fn part_two(
+    (grid, initial_guard_position): &(Vec<Vec<Cell>>, Option<Position>),
+) -> Option<u32> {
     let mut valid_new_obstacle_count = 0;
 
     grid.iter().enumerate().for_each(|(row_index, row)| {
@@ -193,13 +176,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(6));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse);
 
 #[derive(Clone, Copy, Debug)]
 enum Operation {
@@ -19,7 +19,7 @@ impl Operation {
     }
 }
 
-struct Equation {
+pub struct Equation {
     test_value: u64,
     numbers: Vec<u64>,
 }
@@ -65,12 +65,8 @@ fn cartesian_product(values: &[Operation], size: usize) -> Vec<Vec<Operation>> {
     result
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let multiply = Operation::Multiply;
-    let add = Operation::Add;
-    let operations = &[multiply, add];
-
-    let equations: Vec<Equation> = input
+pub fn parse(input: &str) -> Vec<Equation> {
+    input
         .lines()
         .map(|line| {
             let mut parts = line.split(": ");
@@ -94,7 +90,13 @@ pub fn part_one(input: &str) -> Option<u64> {
 
             Equation::new(test_value, &numbers)
         })
-        .collect();
+        .collect()
+}
+
+pub fn part_one(equations: &[Equation]) -> Option<u64> {
+    let multiply = Operation::Multiply;
+    let add = Operation::Add;
+    let operations = &[multiply, add];
 
     let mut total_calibration_result = 0;
 
@@ -111,33 +113,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(total_calibration_result)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let equations: Vec<Equation> = input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split(": ");
-            let test_value = parts
-                .next()
-                .map(|test_value| {
-                    test_value
-                        .parse::<u64>()
-                        .expect("Failed to parse test number")
-                })
-                .expect("Valid test value not found");
-
-            let numbers = parts
-                .next()
-                .map(|nums| {
-                    nums.split_whitespace()
-                        .map(|num_str| num_str.parse::<u64>().expect("Failed to parse numbers"))
-                        .collect::<Vec<u64>>()
-                })
-                .expect("Valid numbers not found.");
-
-            Equation::new(test_value, &numbers)
-        })
-        .collect();
-
+pub fn part_two(equations: &[Equation]) -> Option<u64> {
     let mut total_calibration_result = 0;
 
     let operations = &[
@@ -165,13 +141,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(11387));
     }
 }
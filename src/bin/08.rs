@@ -1,20 +1,20 @@
 use itertools::Itertools;
 use std::collections::HashSet;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct Antenna {
+pub struct Antenna {
     frequency: char,
     position: Position,
 }
 
-enum Cell {
+pub enum Cell {
     Empty,
     Antenna,
 }
 
-struct Grid {
+pub struct Grid {
     cells: Vec<Vec<Cell>>,
 }
 
@@ -34,7 +34,7 @@ impl Distance {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct Position {
+pub struct Position {
     row: usize,
     col: usize,
 }
@@ -76,7 +76,7 @@ impl Position {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn parse(input: &str) -> (Grid, Vec<Antenna>) {
     let mut antennas = vec![];
     let grid = Grid {
         cells: input
@@ -104,6 +104,10 @@ pub fn part_one(input: &str) -> Option<u32> {
             .collect(),
     };
 
+    (grid, antennas)
+}
+
+pub fn part_one((grid, antennas): &(Grid, Vec<Antenna>)) -> Option<u32> {
     let mut antinodes: HashSet<Position> = HashSet::new();
 
     antennas.iter().combinations(2).for_each(|_antennas| {
@@ -115,13 +119,13 @@ pub fn part_one(input: &str) -> Option<u32> {
 
             let smaller_position = a.position.checked_sub(&distance);
             if let Some(smaller) = smaller_position {
-                if smaller.is_valid(&grid) {
+                if smaller.is_valid(grid) {
                     antinodes.insert(smaller);
                 }
             }
             let bigger_position = b.position.checked_add(&distance);
             if let Some(bigger) = bigger_position {
-                if bigger.is_valid(&grid) {
+                if bigger.is_valid(grid) {
                     antinodes.insert(bigger);
                 }
             }
@@ -131,34 +135,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(antinodes.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut antennas = vec![];
-    let grid = Grid {
-        cells: input
-            .lines()
-            .enumerate()
-            .map(|(row_index, line)| {
-                line.chars()
-                    .enumerate()
-                    .map(|(col_index, char)| match char {
-                        '.' => Cell::Empty,
-                        c => {
-                            let antenna = Antenna {
-                                frequency: c,
-                                position: Position {
-                                    row: row_index,
-                                    col: col_index,
-                                },
-                            };
-                            antennas.push(antenna);
-                            Cell::Antenna
-                        }
-                    })
-                    .collect()
-            })
-            .collect(),
-    };
-
+pub fn part_two((grid, antennas): &(Grid, Vec<Antenna>)) -> Option<u32> {
     let mut antinodes: HashSet<Position> = HashSet::new();
 
     antennas.iter().combinations(2).for_each(|_antennas| {
@@ -176,7 +153,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                 let position = a.position.checked_sub(&dist);
 
                 if let Some(smaller) = position {
-                    if smaller.is_valid(&grid) {
+                    if smaller.is_valid(grid) {
                         positions.push(smaller);
                         smaller_count += 1;
                     } else {
@@ -193,7 +170,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                 let position = b.position.checked_add(&dist);
 
                 if let Some(bigger) = position {
-                    if bigger.is_valid(&grid) {
+                    if bigger.is_valid(grid) {
                         positions.push(bigger);
                         bigger_count += 1;
                     } else {
@@ -219,13 +196,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(34));
     }
 }
@@ -74,7 +74,7 @@ impl Game {
                 && next_a.1 <= self.prize.1
                 && visited
                     .get(&next_a)
-                    .map_or(true, |&min_cost| new_cost_a < min_cost)
+                    .is_none_or(|&min_cost| new_cost_a < min_cost)
             {
                 visited.insert(next_a, new_cost_a);
                 queue.push(Reverse((new_cost_a, next_a.0, next_a.1)));
@@ -86,7 +86,7 @@ impl Game {
                 && next_b.1 <= self.prize.1
                 && visited
                     .get(&next_b)
-                    .map_or(true, |&min_cost| new_cost_b < min_cost)
+                    .is_none_or(|&min_cost| new_cost_b < min_cost)
             {
                 visited.insert(next_b, new_cost_b);
                 queue.push(Reverse((new_cost_b, next_b.0, next_b.1)));
@@ -127,19 +127,19 @@ pub fn part_one(input: &str) -> Option<u64> {
                 let determinant = (game.a.x * game.b.y).abs_diff(game.a.y * game.b.x);
 
                 if determinant == 0 {
-                    None
-                } else {
-                    let xfirst = (game.prize.0 * game.b.y).abs_diff(game.prize.1 * game.b.x);
-                    let yfirst = (game.a.x * game.prize.1).abs_diff(game.a.y * game.prize.0);
+                    return None;
+                }
 
-                    let (x, xrem) = (xfirst / determinant, xfirst % determinant);
-                    let (y, yrem) = (yfirst / determinant, yfirst % determinant);
+                let xfirst = (game.prize.0 * game.b.y).abs_diff(game.prize.1 * game.b.x);
+                let yfirst = (game.a.x * game.prize.1).abs_diff(game.a.y * game.prize.0);
 
-                    if xrem == 0 && yrem == 0 {
-                        Some((x, y))
-                    } else {
-                        None
-                    }
+                let (x, xrem) = (xfirst / determinant, xfirst % determinant);
+                let (y, yrem) = (yfirst / determinant, yfirst % determinant);
+
+                if xrem == 0 && yrem == 0 {
+                    Some((x, y))
+                } else {
+                    None
                 }
             })
             .map(|(x, y)| x * 3 + y)
@@ -157,19 +157,19 @@ pub fn part_two(input: &str) -> Option<u64> {
                 let determinant = (game.a.x * game.b.y).abs_diff(game.a.y * game.b.x);
 
                 if determinant == 0 {
-                    None
-                } else {
-                    let xfirst = (game.prize.0 * game.b.y).abs_diff(game.prize.1 * game.b.x);
-                    let yfirst = (game.a.x * game.prize.1).abs_diff(game.a.y * game.prize.0);
+                    return None;
+                }
 
-                    let (x, xrem) = (xfirst / determinant, xfirst % determinant);
-                    let (y, yrem) = (yfirst / determinant, yfirst % determinant);
+                let xfirst = (game.prize.0 * game.b.y).abs_diff(game.prize.1 * game.b.x);
+                let yfirst = (game.a.x * game.prize.1).abs_diff(game.a.y * game.prize.0);
 
-                    if xrem == 0 && yrem == 0 {
-                        Some((x, y))
-                    } else {
-                        None
-                    }
+                let (x, xrem) = (xfirst / determinant, xfirst % determinant);
+                let (y, yrem) = (yfirst / determinant, yfirst % determinant);
+
+                if xrem == 0 && yrem == 0 {
+                    Some((x, y))
+                } else {
+                    None
                 }
             })
             .map(|(x, y)| x * 3 + y)
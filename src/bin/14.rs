@@ -1,3 +1,4 @@
+use advent_of_code::template::Params;
 use glam::IVec2;
 use nom::{
     bytes::complete::tag,
@@ -13,7 +14,28 @@ use nom::{
 // use termion::input::TermRead;
 // use termion::raw::IntoRawMode;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, params = Space);
+
+pub struct Space {
+    width: i32,
+    height: i32,
+}
+
+impl Params for Space {
+    fn real() -> Self {
+        Self {
+            width: 101,
+            height: 103,
+        }
+    }
+
+    fn example() -> Self {
+        Self {
+            width: 11,
+            height: 7,
+        }
+    }
+}
 
 #[derive(Debug)]
 struct Robot {
@@ -59,10 +81,8 @@ fn parse_robot(input: &str) -> IResult<&str, Robot> {
     ))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, &Space { width, height }: &Space) -> Option<u32> {
     let (_, robots) = separated_list1(line_ending, parse_robot)(input).expect("Parse failed");
-    let width = 101;
-    let height = 103;
 
     let mut quadrant_counts = [0; 4];
     robots.iter().for_each(|robot| {
@@ -81,7 +101,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(quadrant_counts.iter().product::<u32>())
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
+pub fn part_two(_input: &str, _space: &Space) -> Option<u32> {
     // let stdin = stdin();
     // let mut stdout = io::stdout().into_raw_mode().unwrap();
 
@@ -152,13 +172,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Space::example(),
+        );
+        assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Space::example(),
+        );
         assert_eq!(result, None);
     }
 }
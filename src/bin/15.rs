@@ -4,10 +4,10 @@ use std::fmt::{Display, Write as _};
 use glam::IVec2;
 use itertools::Itertools;
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(15, parse);
 
-#[derive(Debug)]
-enum Cell {
+#[derive(Clone, Debug)]
+pub enum Cell {
     Empty,
     Wall,
     Box,
@@ -29,7 +29,8 @@ impl Display for Cell {
     }
 }
 
-struct Game {
+#[derive(Clone)]
+pub struct Game {
     grid: Grid<Cell>,
 }
 
@@ -122,7 +123,7 @@ fn get_neighbor_position(position: &IVec2, dir: &Dir) -> IVec2 {
 }
 
 #[derive(Debug)]
-enum Dir {
+pub enum Dir {
     Left,
     Up,
     Right,
@@ -141,9 +142,9 @@ impl Dir {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn parse(input: &str) -> (IVec2, Game, Vec<Dir>) {
     let (game_str, dirs_str) = input.split("\n\n").collect_tuple().unwrap();
-    let (mut character, mut game) = Game::new(game_str);
+    let (character, game) = Game::new(game_str);
     let binding = dirs_str.replace("\n", "");
     let dirs: Vec<Dir> = binding
         .chars()
@@ -156,6 +157,13 @@ pub fn part_one(input: &str) -> Option<u32> {
         })
         .collect();
 
+    (character, game, dirs)
+}
+
+pub fn part_one((character, game, dirs): &(IVec2, Game, Vec<Dir>)) -> Option<u32> {
+    let mut character = *character;
+    let mut game = game.clone();
+
     dirs.iter().for_each(|dir| {
         if let Some(next_pos) = game.checked_move(&character, dir) {
             character = next_pos;
@@ -180,7 +188,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
+pub fn part_two(_input: &(IVec2, Game, Vec<Dir>)) -> Option<u32> {
     None
 }
 
@@ -190,13 +198,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(10092));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, None);
     }
 }
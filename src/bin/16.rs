@@ -1,84 +1,42 @@
-use glam::IVec2;
+use advent_of_code::grid::{Dir, Grid, IVec2};
 use std::{
     cmp::Reverse,
     collections::{BinaryHeap, HashMap},
 };
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, parse);
 
 #[derive(Debug)]
-enum Cell {
+pub enum Cell {
     Empty,
     Wall,
 }
 
-fn parse_grid(input: &str) -> (Vec<Vec<Cell>>, IVec2, IVec2) {
-    let mut from = None;
-    let mut to = None;
-    let grid = input
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '.' => Cell::Empty,
-                    'S' => {
-                        from = Some(IVec2::new(x as i32, y as i32));
-                        Cell::Empty
-                    }
-                    'E' => {
-                        to = Some(IVec2::new(x as i32, y as i32));
-                        Cell::Empty
-                    }
-                    _ => Cell::Wall,
-                })
-                .collect()
-        })
-        .collect();
-
-    (grid, from.unwrap(), to.unwrap())
+pub fn parse(input: &str) -> (Grid<Cell>, IVec2, IVec2) {
+    let (grid, markers) = Grid::parse_with_markers(input, &['S', 'E'], |c| match c {
+        '.' | 'S' | 'E' => Cell::Empty,
+        _ => Cell::Wall,
+    });
+
+    (grid, markers.get('S').unwrap(), markers.get('E').unwrap())
 }
 
-fn get_neighbors(position: &IVec2, grid: &[Vec<Cell>]) -> Vec<(IVec2, Dir)> {
-    let dvecs = [
-        (IVec2::X, Dir::Right),
-        (IVec2::Y, Dir::Down),
-        (IVec2::NEG_X, Dir::Left),
-        (IVec2::NEG_Y, Dir::Up),
-    ];
-    dvecs
+fn get_neighbors(position: &IVec2, grid: &Grid<Cell>) -> Vec<(IVec2, Dir)> {
+    [Dir::Right, Dir::Down, Dir::Left, Dir::Up]
         .into_iter()
-        .filter(|(dvec, _dir)| {
-            let neighbor = *dvec + position;
-            let neighbor_cell = grid
-                .get(neighbor.y as usize)
-                .and_then(|row| row.get(neighbor.x as usize));
-
-            match neighbor_cell {
-                Some(cell) => matches!(cell, Cell::Empty),
-                None => false,
-            }
-        })
+        .map(|dir| (dir.delta(), dir))
+        .filter(|(dvec, _dir)| matches!(grid.get(*dvec + position), Some(Cell::Empty)))
         .collect()
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Clone, Copy)]
-enum Dir {
-    Left,
-    Up,
-    Right,
-    Down,
-}
-
-fn pathfinding(grid: &[Vec<Cell>], from: IVec2, to: IVec2) -> Option<u32> {
+fn pathfinding(grid: &Grid<Cell>, from: IVec2, to: IVec2) -> Option<u32> {
     let mut queue = BinaryHeap::new();
     queue.push(Reverse((0, from.x, from.y, Dir::Right)));
 
     let mut visited: HashMap<(IVec2, Dir), u32> = HashMap::new();
     visited.insert((from, Dir::Right), 0);
 
-    let size = grid.len() * grid[0].len();
+    let size = grid.width() * grid.height();
 
     while let Some(Reverse((cost, x, y, dir))) = queue.pop() {
         let position = IVec2::new(x, y);
@@ -99,7 +57,7 @@ fn pathfinding(grid: &[Vec<Cell>], from: IVec2, to: IVec2) -> Option<u32> {
 
             if visited
                 .get(&(next_position, next_dir))
-                .map_or(true, |&min_cost| next_cost < min_cost)
+                .is_none_or(|&min_cost| next_cost < min_cost)
             {
                 visited.insert((next_position, next_dir), next_cost);
                 queue.push(Reverse((
@@ -120,14 +78,13 @@ fn pathfinding(grid: &[Vec<Cell>], from: IVec2, to: IVec2) -> Option<u32> {
     min.copied()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (grid, from, to) = parse_grid(input);
-    let result = pathfinding(&grid, from, to)?;
+pub fn part_one((grid, from, to): &(Grid<Cell>, IVec2, IVec2)) -> Option<u32> {
+    let result = pathfinding(grid, *from, *to)?;
 
     Some(result)
 }
 
-pub fn part_two(_input: &str) -> Option<u32> {
+pub fn part_two(_input: &(Grid<Cell>, IVec2, IVec2)) -> Option<u32> {
     None
 }
 
@@ -137,13 +94,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(3022));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, None);
     }
 }
@@ -1,17 +1,35 @@
 use std::collections::HashSet;
 
+use advent_of_code::template::Params;
 use itertools::Itertools;
 use pathfinding::prelude::bfs;
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(18, params = Memory);
 
-const SIZE: i32 = 6; // 6 on example, 70 on real input
-const TAKE: usize = 12; // 12 on example, 1024 on real input
+pub struct Memory {
+    /// The largest coordinate of the memory space.
+    size: i32,
+    /// The number of bytes that have fallen in part one.
+    take: usize,
+}
+
+impl Params for Memory {
+    fn real() -> Self {
+        Self {
+            size: 70,
+            take: 1024,
+        }
+    }
+
+    fn example() -> Self {
+        Self { size: 6, take: 12 }
+    }
+}
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, &Memory { size, take }: &Memory) -> Option<u32> {
     let corrupteds = input
         .lines()
-        .take(TAKE)
+        .take(take)
         .map(|line| {
             line.split(",")
                 .map(|s| s.parse::<i32>().unwrap())
@@ -21,14 +39,14 @@ pub fn part_one(input: &str) -> Option<u32> {
         .collect::<HashSet<_>>();
 
     let start = (0, 0);
-    let goal = (SIZE, SIZE);
+    let goal = (size, size);
 
     let result = bfs(
         &start,
         |&(x, y)| {
             let a: Vec<(i32, i32)> = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
                 .iter()
-                .filter(|(x, y)| *x >= 0 && *x <= SIZE && *y >= 0 && *y <= SIZE)
+                .filter(|(x, y)| *x >= 0 && *x <= size && *y >= 0 && *y <= size)
                 .filter(|(x, y)| !corrupteds.contains(&(*x, *y)))
                 .map(|(x, y)| (*x, *y))
                 .collect();
@@ -44,7 +62,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str, &Memory { size, .. }: &Memory) -> Option<(i32, i32)> {
     let all_corrupteds = input
         .lines()
         .map(|line| {
@@ -60,8 +78,8 @@ pub fn part_two(input: &str) -> Option<String> {
 
     loop {
         let mid = (left + right) / 2;
-        let first = check_take(&all_corrupteds, mid);
-        let second = check_take(&all_corrupteds, mid + 1);
+        let first = check_take(&all_corrupteds, mid, size);
+        let second = check_take(&all_corrupteds, mid + 1, size);
         match (first.is_some(), second.is_some()) {
             // too left
             (true, true) => left = mid + 1,
@@ -74,13 +92,13 @@ pub fn part_two(input: &str) -> Option<String> {
         }
     }
 
-    Some(input.lines().nth((left + right) / 2).unwrap().to_string())
+    Some(all_corrupteds[(left + right) / 2])
 }
 
-fn check_take(all_corrupteds: &[(i32, i32)], take: usize) -> Option<Vec<(i32, i32)>> {
+fn check_take(all_corrupteds: &[(i32, i32)], take: usize, size: i32) -> Option<Vec<(i32, i32)>> {
     let corrupteds: HashSet<&(i32, i32)> = all_corrupteds.iter().take(take).collect();
     let start = (0, 0);
-    let goal = (SIZE, SIZE);
+    let goal = (size, size);
 
     bfs(
         &start,
@@ -89,9 +107,9 @@ fn check_take(all_corrupteds: &[(i32, i32)], take: usize) -> Option<Vec<(i32, i3
                 .iter()
                 .filter(|(x, y)| {
                     *x >= 0
-                        && *x <= SIZE
+                        && *x <= size
                         && *y >= 0
-                        && *y <= SIZE
+                        && *y <= size
                         && !corrupteds.contains(&(*x, *y))
                 })
                 .map(|(x, y)| (*x, *y))
@@ -107,13 +125,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Memory::example(),
+        );
         assert_eq!(result, Some(22));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("6,1".to_string()));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Memory::example(),
+        );
+        assert_eq!(result, Some((6, 1)));
     }
 }
@@ -1,17 +1,48 @@
+use advent_of_code::template::{examples::Example, Params};
 use glam::UVec2;
 use itertools::Itertools;
 use pathfinding::prelude::bfs;
 use std::collections::HashMap;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, parse, params = Race);
+
+pub struct Race {
+    /// The minimum number of picoseconds that a cheat has to save to be counted.
+    min_saving: usize,
+}
+
+impl Params for Race {
+    fn real() -> Self {
+        Self { min_saving: 100 }
+    }
+
+    fn example() -> Self {
+        Self { min_saving: 50 }
+    }
+
+    fn for_example(example: &Example) -> Self {
+        Self {
+            min_saving: example
+                .param("min_saving")
+                .unwrap_or(Self::example().min_saving),
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Cell {
+pub enum Cell {
     Wall,
     Empty,
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub struct RaceTrack {
+    track: Vec<Vec<Cell>>,
+    start: UVec2,
+    goal: UVec2,
+    walls: Vec<UVec2>,
+}
+
+pub fn parse(input: &str) -> RaceTrack {
     let mut start: Option<UVec2> = None;
     let mut goal: Option<UVec2> = None;
 
@@ -41,15 +72,29 @@ pub fn part_one(input: &str) -> Option<u32> {
         })
         .collect();
 
-    let start = start.unwrap();
-    let goal = goal.unwrap();
+    RaceTrack {
+        track,
+        start: start.unwrap(),
+        goal: goal.unwrap(),
+        walls,
+    }
+}
 
-    let cheatless_route = solve(&track, &start, &goal, &UVec2::new(0, 0)).unwrap();
+pub fn part_one(
+    RaceTrack {
+        track,
+        start,
+        goal,
+        walls,
+    }: &RaceTrack,
+    &Race { min_saving }: &Race,
+) -> Option<u32> {
+    let cheatless_route = solve(track, start, goal, &UVec2::new(0, 0)).unwrap();
 
     let mut map: HashMap<usize, u32> = HashMap::new();
 
     walls.iter().for_each(|wall| {
-        let result = solve(&track, &start, &goal, wall).unwrap().len();
+        let result = solve(track, start, goal, wall).unwrap().len();
         let value = map.get(&result);
         match value {
             Some(n) => map.insert(result, n + 1),
@@ -59,7 +104,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
     Some(
         map.iter()
-            .filter(|&(pico, _)| cheatless_route.len() - pico >= 100)
+            .filter(|&(pico, _)| cheatless_route.len() - pico >= min_saving)
             .map(|(_, count)| *count)
             .sum(),
     )
@@ -80,7 +125,7 @@ fn solve(track: &[Vec<Cell>], start: &UVec2, goal: &UVec2, cheat: &UVec2) -> Opt
                 track
                     .get(position.y as usize)
                     .and_then(|row| row.get(position.x as usize))
-                    .map_or(false, |c| {
+                    .is_some_and(|c| {
                         if cheat.x == position.x && cheat.y == position.y {
                             true
                         } else {
@@ -94,36 +139,13 @@ fn solve(track: &[Vec<Cell>], start: &UVec2, goal: &UVec2, cheat: &UVec2) -> Opt
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut start: Option<UVec2> = None;
-    let mut goal: Option<UVec2> = None;
-
-    let track: Vec<Vec<Cell>> = input
-        .lines()
-        .enumerate()
-        .map(|(y, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(x, c)| match c {
-                    '#' => Cell::Wall,
-                    'S' => {
-                        start = Some(UVec2::new(x as u32, y as u32));
-                        Cell::Empty
-                    }
-                    'E' => {
-                        goal = Some(UVec2::new(x as u32, y as u32));
-                        Cell::Empty
-                    }
-                    _ => Cell::Empty,
-                })
-                .collect()
-        })
-        .collect();
-
-    let start = start.unwrap();
-    let goal = goal.unwrap();
-
-    let cheatless_route = solve(&track, &start, &goal, &UVec2::new(0, 0)).unwrap();
+pub fn part_two(
+    RaceTrack {
+        track, start, goal, ..
+    }: &RaceTrack,
+    &Race { min_saving }: &Race,
+) -> Option<u32> {
+    let cheatless_route = solve(track, start, goal, &UVec2::new(0, 0)).unwrap();
 
     let mut map: HashMap<usize, u32> = HashMap::new();
     cheatless_route
@@ -146,7 +168,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     Some(
         map.iter()
-            .filter(|&(pico, _)| cheatless_route.len() - pico >= 100)
+            .filter(|&(pico, _)| cheatless_route.len() - pico >= min_saving)
             .map(|(_, count)| *count)
             .sum(),
     )
@@ -156,15 +178,5 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(0));
-    }
+    advent_of_code::examples!(parse, params = Race; saves_64, saves_20, long_cheats_save_76, long_cheats_save_50);
 }
@@ -0,0 +1,355 @@
+//! A 2D grid of cells for puzzles whose input is a map of characters.
+//!
+//! Positions are [`IVec2`]s, with `x` as the column and `y` as the row, so `Dir::Down` increases `y`.
+//! The cells are stored in a [`grid::Grid`], which is available through [`Grid::inner`].
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+pub use glam::IVec2;
+
+/// The offsets of the 4 orthogonal neighbors, clockwise from the top.
+pub const NEIGHBORS_4: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// The offsets of the 8 orthogonal and diagonal neighbors, clockwise from the top.
+pub const NEIGHBORS_8: [IVec2; 8] = [
+    IVec2::NEG_Y,
+    IVec2::new(1, -1),
+    IVec2::X,
+    IVec2::new(1, 1),
+    IVec2::Y,
+    IVec2::new(-1, 1),
+    IVec2::NEG_X,
+    IVec2::new(-1, -1),
+];
+
+/// One of the 4 orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    /// All directions, clockwise from the top.
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    /// The offset of a step in this direction.
+    pub fn delta(self) -> IVec2 {
+        match self {
+            Dir::Up => IVec2::NEG_Y,
+            Dir::Right => IVec2::X,
+            Dir::Down => IVec2::Y,
+            Dir::Left => IVec2::NEG_X,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Dir::Up => Dir::Right,
+            Dir::Right => Dir::Down,
+            Dir::Down => Dir::Left,
+            Dir::Left => Dir::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        self.opposite().turn_right()
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// Reads an arrow, i.e. `^`, `>`, `v` or `<`.
+    pub fn from_arrow(arrow: char) -> Option<Self> {
+        match arrow {
+            '^' => Some(Dir::Up),
+            '>' => Some(Dir::Right),
+            'v' => Some(Dir::Down),
+            '<' => Some(Dir::Left),
+            _ => None,
+        }
+    }
+}
+
+/// The positions of marker characters, e.g. the `S` start and the `E` end of a maze.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Markers(HashMap<char, Vec<IVec2>>);
+
+impl Markers {
+    /// The first position of a marker, in reading order.
+    pub fn get(&self, marker: char) -> Option<IVec2> {
+        self.all(marker).first().copied()
+    }
+
+    /// All positions of a marker, in reading order.
+    pub fn all(&self, marker: char) -> &[IVec2] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// A rectangular grid of cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T>(grid::Grid<T>);
+
+impl<T> Grid<T> {
+    /// Parse a grid with one row per line, mapping each character to a cell. Panics if the rows differ in length.
+    pub fn parse(input: &str, cell: impl FnMut(char) -> T) -> Self {
+        Self::parse_with_markers(input, &[], cell).0
+    }
+
+    /// Parse a grid like [`Grid::parse`], and collect the positions of the `markers`.
+    ///
+    /// Markers are mapped to cells like any other character, e.g. `S` to an empty cell.
+    pub fn parse_with_markers(
+        input: &str,
+        markers: &[char],
+        mut cell: impl FnMut(char) -> T,
+    ) -> (Self, Markers) {
+        let mut positions = Markers::default();
+        let mut cells = vec![];
+        let mut width = None;
+
+        for (y, line) in input.lines().enumerate() {
+            let mut len = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                if markers.contains(&c) {
+                    let position = IVec2::new(x as i32, y as i32);
+                    positions.0.entry(c).or_default().push(position);
+                }
+                cells.push(cell(c));
+                len += 1;
+            }
+
+            match width {
+                None => width = Some(len),
+                Some(width) => assert_eq!(
+                    len, width,
+                    "row {y} has {len} cells, but the first row has {width}"
+                ),
+            }
+        }
+
+        (
+            Self(grid::Grid::from_vec(cells, width.unwrap_or(0))),
+            positions,
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.0.cols()
+    }
+
+    pub fn height(&self) -> usize {
+        self.0.rows()
+    }
+
+    /// Returns `true` if the position is inside of the grid.
+    pub fn contains(&self, position: IVec2) -> bool {
+        self.index_of(position).is_some()
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<&T> {
+        let (row, col) = self.index_of(position)?;
+        self.0.get(row, col)
+    }
+
+    pub fn get_mut(&mut self, position: IVec2) -> Option<&mut T> {
+        let (row, col) = self.index_of(position)?;
+        self.0.get_mut(row, col)
+    }
+
+    /// Swaps the cells at two positions. Panics if one of them is outside of the grid.
+    pub fn swap(&mut self, a: IVec2, b: IVec2) {
+        let a = self.index_of(a).expect("position is outside of the grid");
+        let b = self.index_of(b).expect("position is outside of the grid");
+        self.0.swap(a, b);
+    }
+
+    /// All positions of the grid, in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        let width = self.width();
+        (0..self.height())
+            .flat_map(move |y| (0..width).map(move |x| IVec2::new(x as i32, y as i32)))
+    }
+
+    /// All cells with their positions, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.positions().map(|position| (position, &self[position]))
+    }
+
+    /// The positions of all cells matching a predicate, in reading order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = IVec2> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The orthogonal neighbors of a position that are inside of the grid.
+    pub fn neighbors_4(&self, position: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBORS_4
+            .into_iter()
+            .map(move |delta| position + delta)
+            .filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// The orthogonal and diagonal neighbors of a position that are inside of the grid.
+    pub fn neighbors_8(&self, position: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBORS_8
+            .into_iter()
+            .map(move |delta| position + delta)
+            .filter(|neighbor| self.contains(*neighbor))
+    }
+
+    /// Maps each cell to a new cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid(grid::Grid::from_vec(
+            self.0.iter_rows().flatten().map(f).collect(),
+            self.width(),
+        ))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    pub fn rotate_right(&mut self) {
+        self.0.rotate_right();
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    pub fn rotate_left(&mut self) {
+        self.0.rotate_left();
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&mut self) {
+        self.0.transpose();
+    }
+
+    /// The underlying [`grid::Grid`], indexed by `(row, col)`.
+    pub fn inner(&self) -> &grid::Grid<T> {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> grid::Grid<T> {
+        self.0
+    }
+
+    fn index_of(&self, position: IVec2) -> Option<(usize, usize)> {
+        let col = usize::try_from(position.x).ok()?;
+        let row = usize::try_from(position.y).ok()?;
+        (col < self.width() && row < self.height()).then_some((row, col))
+    }
+}
+
+impl<T> From<grid::Grid<T>> for Grid<T> {
+    fn from(grid: grid::Grid<T>) -> Self {
+        Self(grid)
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    /// Panics if the position is outside of the grid.
+    fn index(&self, position: IVec2) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, position: IVec2) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position} is outside of the grid"))
+    }
+}
+
+/// Renders one line per row, with the cells written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.0.iter_rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir, Grid, IVec2};
+
+    const MAZE: &str = "#####\n#S..#\n#.#E#\n#####\n";
+
+    #[test]
+    fn parses_grids_with_markers() {
+        let (grid, markers) = Grid::parse_with_markers(MAZE, &['S', 'E', 'X'], |c| c == '#');
+
+        assert_eq!((grid.width(), grid.height()), (5, 4));
+        assert_eq!(markers.get('S'), Some(IVec2::new(1, 1)));
+        assert_eq!(markers.get('E'), Some(IVec2::new(3, 2)));
+        assert_eq!(markers.get('X'), None);
+        assert_eq!(grid.find_all(|wall| !wall).count(), 5);
+
+        assert!(grid[IVec2::new(2, 2)]);
+        assert_eq!(grid.get(IVec2::new(-1, 0)), None);
+        assert_eq!(grid.get(IVec2::new(5, 0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 1 has 2 cells, but the first row has 3")]
+    fn panics_for_ragged_rows() {
+        Grid::parse("abc\nab\n", |c| c);
+    }
+
+    #[test]
+    fn iterates_neighbors_inside_of_the_grid() {
+        let grid = Grid::parse("abc\ndef\nghi\n", |c| c);
+
+        let neighbors =
+            |position: IVec2| -> String { grid.neighbors_4(position).map(|p| grid[p]).collect() };
+        assert_eq!(neighbors(IVec2::new(1, 1)), "bfhd");
+        assert_eq!(neighbors(IVec2::new(0, 0)), "bd");
+
+        let neighbors: String = grid.neighbors_8(IVec2::ZERO).map(|p| grid[p]).collect();
+        assert_eq!(neighbors, "bed");
+    }
+
+    #[test]
+    fn rotates_and_renders() {
+        let mut grid = Grid::parse("ab\ncd\nef\n", |c| c);
+
+        grid.rotate_right();
+        assert_eq!(grid.to_string(), "eca\nfdb\n");
+        assert_eq!(grid[IVec2::new(2, 0)], 'a');
+
+        grid.rotate_left();
+        grid.transpose();
+        assert_eq!(grid.to_string(), "ace\nbdf\n");
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ACE\nBDF\n"
+        );
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Dir::Up.turn_right(), Dir::Right);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+        assert_eq!(Dir::Left.opposite(), Dir::Right);
+        assert_eq!(Dir::from_arrow('v').map(Dir::delta), Some(IVec2::Y));
+    }
+}
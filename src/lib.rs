@@ -1,3 +1,4 @@
+pub mod grid;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
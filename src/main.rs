@@ -1,81 +1,203 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, check_answers, download, export, extract_examples, read, scaffold, solve, time, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{templates::DEFAULT_TEMPLATE, Day, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        bench::{parse_duration, BenchOptions},
+        export::ExportFormat,
+        templates::DEFAULT_TEMPLATE,
+        Day, Puzzle, Timeouts, Year,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
+            extract_examples: bool,
+            overwrite: bool,
+            template: String,
+        },
+        ExtractExamples {
+            puzzle: Puzzle,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
+            count_allocs: bool,
             submit: Option<u8>,
+            time: bool,
+            bench: BenchOptions,
         },
         All {
+            year: Option<Year>,
+            release: bool,
+            dhat: bool,
+            count_allocs: bool,
+            jobs: usize,
+            timeouts: Timeouts,
+        },
+        CheckAnswers {
+            year: Option<Year>,
             release: bool,
+            jobs: usize,
+            timeouts: Timeouts,
+        },
+        Export {
+            year: Option<Year>,
+            format: ExportFormat,
+            path: String,
+            release: bool,
+            time: bool,
+            jobs: usize,
+            timeouts: Timeouts,
+            bench: BenchOptions,
         },
         Time {
+            year: Option<Year>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            profile: Option<String>,
+            dhat: bool,
+            count_allocs: bool,
+            timeouts: Timeouts,
+            bench: BenchOptions,
+        },
+        Watch {
+            puzzle: Puzzle,
+            release: bool,
+            interval: Duration,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<Year>,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        // `--year` selects the event for every subcommand, the configured `AOC_YEAR` is used by default.
+        let year = Year::explicit(args.opt_value_from_str("--year")?);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
+                release: args.contains("--release"),
+                dhat: args.contains("--dhat"),
+                count_allocs: args.contains("--count-allocs"),
+                jobs: parse_jobs(&mut args)?,
+                timeouts: Timeouts::from_args(&mut args)?,
+            },
+            Some("check-answers") => AppArguments::CheckAnswers {
+                year,
                 release: args.contains("--release"),
+                jobs: parse_jobs(&mut args)?,
+                timeouts: Timeouts::from_args(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: f64 = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let profile = args.opt_value_from_str("--profile")?;
+                let dhat = args.contains("--dhat");
+                let count_allocs = args.contains("--count-allocs");
+                let timeouts = Timeouts::from_args(&mut args)?;
+                let bench = BenchOptions::from_args(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    // the threshold is given in percent.
+                    compare: compare.then_some(threshold / 100.0),
+                    profile,
+                    dhat,
+                    count_allocs,
+                    timeouts,
+                    bench,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
                 download: args.contains("--download"),
+                extract_examples: args.contains("--extract-examples"),
                 overwrite: args.contains("--overwrite"),
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string()),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("extract-examples") => AppArguments::ExtractExamples {
+                puzzle: Puzzle::new(year, args.free_from_str()?),
+                overwrite: args.contains("--overwrite"),
+            },
+            Some("export") => {
+                let release = args.contains("--release");
+                let time = args.contains("--time");
+                let jobs = parse_jobs(&mut args)?;
+                let timeouts = Timeouts::from_args(&mut args)?;
+                let bench = BenchOptions::from_args(&mut args)?;
+
+                AppArguments::Export {
+                    year,
+                    format: args.free_from_str()?,
+                    path: args.free_from_str()?,
+                    release,
+                    time,
+                    jobs,
+                    timeouts,
+                    bench,
+                }
+            }
+            Some("solve") => {
+                let bench = BenchOptions::from_args(&mut args)?;
+
+                AppArguments::Solve {
+                    puzzle: Puzzle::new(year, args.free_from_str()?),
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    count_allocs: args.contains("--count-allocs"),
+                    time: args.contains("--time"),
+                    bench,
+                }
+            }
+            Some("watch") => AppArguments::Watch {
+                puzzle: Puzzle::new(year, args.free_from_str()?),
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                interval: args
+                    .opt_value_from_fn("--interval", parse_duration)?
+                    .unwrap_or(Duration::from_millis(500)),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today { year },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -86,6 +208,14 @@ mod args {
             }
         };
 
+        if let AppArguments::Time { bench, .. }
+        | AppArguments::Solve { bench, .. }
+        | AppArguments::Export { bench, .. } = &app_args
+        {
+            // fail early instead of inside the solution binaries.
+            bench.clone().or(BenchOptions::from_env()?).resolve()?;
+        }
+
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
@@ -93,6 +223,15 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse the number of days to run concurrently, defaults to one.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str::<_, usize>("--jobs")? {
+            Some(0) => Err("`--jobs` must be at least 1.".into()),
+            Some(jobs) => Ok(jobs),
+            None => Ok(1),
+        }
+    }
 }
 
 fn main() {
@@ -102,33 +241,95 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All {
+                year,
+                release,
+                dhat,
+                count_allocs,
+                jobs,
+                timeouts,
+            } => all::handle(year, release, dhat, count_allocs, jobs, timeouts),
+            AppArguments::CheckAnswers {
+                year,
+                release,
+                jobs,
+                timeouts,
+            } => check_answers::handle(year, release, jobs, timeouts),
+            AppArguments::Export {
+                year,
+                format,
+                path,
+                release,
+                time,
+                jobs,
+                timeouts,
+                bench,
+            } => export::handle(year, format, &path, release, time, jobs, timeouts, &bench),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                compare,
+                profile,
+                dhat,
+                count_allocs,
+                timeouts,
+                bench,
+            } => time::handle(
+                year,
+                day,
+                all,
+                store,
+                compare,
+                profile,
+                dhat,
+                count_allocs,
+                timeouts,
+                &bench,
+            ),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
+                extract_examples,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, &template, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
+                }
+                if extract_examples {
+                    extract_examples::handle(puzzle, overwrite);
                 }
             }
+            AppArguments::ExtractExamples { puzzle, overwrite } => {
+                extract_examples::handle(puzzle, overwrite)
+            }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
+                count_allocs,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                time,
+                bench,
+            } => solve::handle(puzzle, release, dhat, count_allocs, submit, time, &bench),
+            AppArguments::Watch {
+                puzzle,
+                release,
+                interval,
+            } => watch::handle(puzzle, release, interval),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
+            AppArguments::Today { year } => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::new(year, day);
+                        scaffold::handle(puzzle, DEFAULT_TEMPLATE, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,116 @@
+//! A global allocator that counts the allocations of a solution.
+//!
+//! Installed by the [`solution!`](crate::solution) macro when the `count-allocs` feature is enabled.
+//! Unlike dhat, it only keeps a few counters and works with every cargo profile.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::template::stats::MemoryStats;
+
+static BLOCKS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+/// Live bytes when the counters were last reset.
+static BASELINE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts allocated blocks, allocated bytes and peak live bytes.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // NOTE: like dhat, a reallocation counts as a new block.
+            record_alloc(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize, freed: usize) {
+    BLOCKS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+
+    let live = LIVE_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    LIVE_BYTES.fetch_sub(freed as u64, Ordering::Relaxed);
+}
+
+/// Start counting from scratch. Memory that is live at this point is not included in the peak.
+pub fn reset() {
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+
+    BLOCKS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    BASELINE_BYTES.store(live, Ordering::Relaxed);
+    PEAK_BYTES.store(live, Ordering::Relaxed);
+}
+
+/// Allocations since the last [`reset`].
+pub fn stats() -> MemoryStats {
+    MemoryStats {
+        blocks: BLOCKS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK_BYTES
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE_BYTES.load(Ordering::Relaxed)),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{reset, stats, CountingAllocator};
+    use crate::template::stats::MemoryStats;
+    use std::alloc::{GlobalAlloc, Layout};
+
+    #[test]
+    fn counts_allocations() {
+        let small = Layout::from_size_align(16, 8).unwrap();
+        let large = Layout::from_size_align(64, 8).unwrap();
+
+        // NOTE: the allocator is not installed in tests, only the calls below are counted.
+        reset();
+
+        unsafe {
+            let a = CountingAllocator.alloc(small);
+            let b = CountingAllocator.alloc_zeroed(small);
+            CountingAllocator.dealloc(a, small);
+            let b = CountingAllocator.realloc(b, small, 64);
+            CountingAllocator.dealloc(b, large);
+        }
+
+        assert_eq!(
+            stats(),
+            MemoryStats {
+                blocks: 3,
+                bytes: 96,
+                peak_bytes: 80,
+            }
+        );
+    }
+}
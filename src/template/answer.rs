@@ -0,0 +1,281 @@
+//! Answers of solution parts, normalised to the string that is printed, submitted and stored in the answer registry.
+
+use std::fmt::Display;
+
+use glam::{I64Vec2, IVec2, UVec2};
+
+/// The answer of a solution part.
+///
+/// Parts may return anything that converts into an [`Answer`]: integers, strings, coordinates or
+/// multi-line ASCII art. The [canonical](Answer::canonical) string of an answer is what gets printed,
+/// submitted and compared with the answer registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// A coordinate, written as `x,y`.
+    Coord(i64, i64),
+    /// Letters drawn as ASCII art, with the text they were read as, if known.
+    Art {
+        art: String,
+        text: Option<String>,
+    },
+}
+
+impl Answer {
+    /// ASCII art, read with the built-in OCR for the common 4x6 Advent of Code font.
+    pub fn art(art: impl Into<String>) -> Self {
+        Self::art_with(art, read_letters)
+    }
+
+    /// ASCII art, read with a custom OCR hook, e.g. for a font that the built-in OCR does not know.
+    pub fn art_with(art: impl Into<String>, ocr: impl FnOnce(&str) -> Option<String>) -> Self {
+        let art = art.into();
+        let text = ocr(&art);
+        Self::Art { art, text }
+    }
+
+    /// The normalised answer. Art that could not be read stays multi-line.
+    pub fn canonical(&self) -> String {
+        match self {
+            Answer::Integer(n) => n.to_string(),
+            Answer::Text(text) => text.trim().to_string(),
+            Answer::Coord(x, y) => format!("{x},{y}"),
+            Answer::Art {
+                text: Some(text), ..
+            } => text.clone(),
+            Answer::Art { art, text: None } => art.trim_end().to_string(),
+        }
+    }
+
+    /// Returns `true` if the answer can be submitted, i.e. its canonical string is a single line.
+    pub fn is_submittable(&self) -> bool {
+        let canonical = self.canonical();
+        !canonical.is_empty() && !canonical.contains('\n')
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.canonical())
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Integer(value.into())
+                }
+            }
+
+            impl From<($t, $t)> for Answer {
+                fn from((x, y): ($t, $t)) -> Self {
+                    Answer::Coord(coordinate(x), coordinate(y))
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Integer(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Integer)
+    }
+}
+
+impl From<(usize, usize)> for Answer {
+    fn from((x, y): (usize, usize)) -> Self {
+        Answer::Coord(coordinate(x), coordinate(y))
+    }
+}
+
+/// Converts a coordinate. Panics instead of wrapping around if it does not fit into an `i64`.
+fn coordinate<T: TryInto<i64> + Display + Copy>(value: T) -> i64 {
+    value
+        .try_into()
+        .unwrap_or_else(|_| panic!("coordinate {value} does not fit into an i64"))
+}
+
+impl From<IVec2> for Answer {
+    fn from(value: IVec2) -> Self {
+        Answer::Coord(value.x.into(), value.y.into())
+    }
+}
+
+impl From<I64Vec2> for Answer {
+    fn from(value: I64Vec2) -> Self {
+        Answer::Coord(value.x, value.y)
+    }
+}
+
+impl From<UVec2> for Answer {
+    fn from(value: UVec2) -> Self {
+        Answer::Coord(value.x.into(), value.y.into())
+    }
+}
+
+/// Strings with several lines are treated as ASCII art.
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        if value.trim().contains('\n') {
+            Answer::art(value)
+        } else {
+            Answer::Text(value)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        value.to_string().into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The letters of the font that most Advent of Code puzzles draw with: 4 pixels wide, 6 high, one column apart.
+const LETTERS: [(char, [&str; 6]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Read letters drawn in the 4x6 font. Any character other than `.` and whitespace is a lit pixel.
+///
+/// Returns `None` if the art is not 6 lines high or contains an unknown letter.
+pub fn read_letters(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .skip_while(|line| line.trim().is_empty())
+        .map(|line| {
+            line.chars()
+                .map(|c| c != '.' && !c.is_whitespace())
+                .collect()
+        })
+        .collect();
+
+    let rows: Vec<&[bool]> = rows
+        .iter()
+        .map(Vec::as_slice)
+        .take_while(|row| !row.is_empty() && row.iter().any(|lit| *lit))
+        .collect();
+
+    if rows.len() != 6 {
+        return None;
+    }
+
+    let width = rows.iter().map(|row| row.len()).max()?;
+    let lit = |row: &[bool], col: usize| row.get(col).copied().unwrap_or(false);
+
+    let mut text = String::new();
+
+    for start in (0..width).step_by(5) {
+        let glyph: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                (start..start + 4)
+                    .map(|col| if lit(row, col) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        // NOTE: trailing columns without any lit pixels are padding.
+        if glyph.iter().all(|row| row == "....") {
+            continue;
+        }
+
+        let (letter, _) = LETTERS.iter().find(|(_, pixels)| glyph == pixels)?;
+        text.push(*letter);
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_letters, Answer};
+    use glam::IVec2;
+
+    const ART: &str = "
+#..#..###
+#..#...#.
+####...#.
+#..#...#.
+#..#...#.
+#..#..###
+";
+
+    #[test]
+    fn normalises_answers() {
+        assert_eq!(Answer::from(42u32).canonical(), "42");
+        assert_eq!(Answer::from(-7i64).canonical(), "-7");
+        assert_eq!(Answer::from(u128::MAX).canonical(), u128::MAX.to_string());
+        assert_eq!(Answer::from("4,6,3 \n").canonical(), "4,6,3");
+        assert_eq!(Answer::from((6i32, 1i32)).canonical(), "6,1");
+        assert_eq!(Answer::from(IVec2::new(-1, 2)).canonical(), "-1,2");
+    }
+
+    #[test]
+    #[should_panic(expected = "coordinate 18446744073709551615 does not fit into an i64")]
+    fn panics_for_wrapping_coordinates() {
+        let _ = Answer::from((u64::MAX, 0));
+    }
+
+    #[test]
+    fn reads_ascii_art() {
+        assert_eq!(read_letters(ART).as_deref(), Some("HI"));
+        assert_eq!(
+            read_letters(&ART.replace('#', "█").replace('.', " ")).as_deref(),
+            Some("HI")
+        );
+
+        let answer = Answer::from(ART.to_string());
+        assert_eq!(answer.canonical(), "HI");
+        assert!(answer.is_submittable());
+    }
+
+    #[test]
+    fn keeps_unreadable_art() {
+        let art = "#.#\n.#.\n#.#\n";
+        assert_eq!(read_letters(art), None);
+
+        let answer = Answer::from(art);
+        assert_eq!(answer.canonical(), "#.#\n.#.\n#.#");
+        assert!(!answer.is_submittable());
+
+        let answer = Answer::art_with(art, |_| Some("X".into()));
+        assert_eq!(answer.canonical(), "X");
+    }
+}
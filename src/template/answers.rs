@@ -0,0 +1,273 @@
+//! Registry of accepted answers, used to detect regressions in solutions.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Error},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{data_path, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayAnswers {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<DayAnswers>,
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(data_path(year, ANSWERS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns empty answers.
+    /// Fails if the file can not be read or parsed, so that a corrupt registry is not overwritten.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        let path = data_path(year, ANSWERS_FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Ok(json) => {
+                Answers::try_from(json).map_err(|e| format!("failed to parse \"{path}\": {e}"))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("failed to read \"{path}\": {e}")),
+        }
+    }
+
+    /// Returns the accepted answer for a part, if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answers = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answers.part_1.as_deref(),
+            2 => answers.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records an accepted answer for a part, overwriting a previous one.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(DayAnswers {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        match part {
+            1 => self.data[index].part_1 = Some(answer.into()),
+            2 => self.data[index].part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of comparing a computed answer with the registry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerCheck {
+    /// The computed answer matches the accepted one.
+    Pass,
+    /// The computed answer differs from the accepted one, or there is no computed answer.
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No accepted answer is stored for this part.
+    Missing,
+}
+
+impl Answers {
+    pub fn check(&self, day: Day, part: u8, actual: Option<&str>) -> AnswerCheck {
+        match (self.get(day, part), actual) {
+            (None, _) => AnswerCheck::Missing,
+            (Some(expected), Some(actual)) if expected == actual => AnswerCheck::Pass,
+            (Some(expected), actual) => AnswerCheck::Fail {
+                expected: expected.into(),
+                actual: actual.map(Into::into),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(DayAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayAnswers> for JsonValue {
+    fn from(value: &DayAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for DayAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answers to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answers.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answers.part_2 to be null or string.")?;
+
+        Ok(DayAnswers {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AnswerCheck, Answers};
+    use crate::day;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "11", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("11"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26" }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 2, "foo");
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(1), 1, "43");
+
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+
+        assert_eq!(parsed, answers);
+        assert_eq!(parsed.data.len(), 2);
+        assert_eq!(parsed.data[0].day, day!(1));
+        assert_eq!(parsed.get(day!(1), 1), Some("43"));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+
+        assert_eq!(answers.check(day!(1), 1, Some("42")), AnswerCheck::Pass);
+        assert_eq!(
+            answers.check(day!(1), 1, Some("41")),
+            AnswerCheck::Fail {
+                expected: "42".into(),
+                actual: Some("41".into())
+            }
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, None),
+            AnswerCheck::Fail {
+                expected: "42".into(),
+                actual: None
+            }
+        );
+        assert_eq!(answers.check(day!(1), 2, Some("42")), AnswerCheck::Missing);
+    }
+}
@@ -1,125 +1,197 @@
 /// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
+///
+/// If the `client` feature is enabled, the native client in [`super::aoc_client`] is used instead.
+use std::{fmt::Display, fs, io, path::Path, process::Output};
 
-use crate::template::Day;
+use crate::template::Puzzle;
+
+#[cfg(not(feature = "client"))]
+pub use cli::{check, download, read, submit};
+
+#[cfg(feature = "client")]
+pub use super::aoc_client::{check, download, read, submit};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    /// A required setting (e.g. the session cookie) is not configured.
+    MissingConfig(String),
+    /// The session cookie was rejected by the server.
+    Unauthorised,
+    /// The puzzle for this day has not been released yet.
+    NotYetUnlocked,
+    /// The server asked us to wait before trying again.
+    RateLimited(String),
+    /// The server answered with a message that we do not understand.
+    UnexpectedResponse(String),
+    Network(String),
+    IO(std::io::Error),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(
+                f,
+                "aoc-cli is not present in environment. Try running \"cargo install aoc-cli\" to install it."
+            ),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::MissingConfig(s) => write!(f, "missing configuration: {s}"),
+            AocCommandError::Unauthorised => write!(
+                f,
+                "the session cookie was rejected. Please refresh your session cookie."
+            ),
+            AocCommandError::NotYetUnlocked => {
+                write!(f, "this puzzle has not been unlocked yet.")
+            }
+            AocCommandError::RateLimited(s) => write!(f, "rate limited: {s}"),
+            AocCommandError::UnexpectedResponse(s) => {
+                write!(f, "unexpected response: {}", s.trim())
+            }
+            AocCommandError::Network(s) => write!(f, "network error: {s}"),
+            AocCommandError::IO(e) => write!(f, "io error: {e}"),
         }
     }
 }
 
-pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+impl From<std::io::Error> for AocCommandError {
+    fn from(e: std::io::Error) -> Self {
+        AocCommandError::IO(e)
+    }
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
-
-    call_aoc_cli(&args)
+/// Returns `true` if the response to a `submit` call indicates that the answer was accepted.
+pub fn is_correct_answer(response: &str) -> bool {
+    response.contains("That's the right answer")
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
+pub(crate) fn get_input_path(puzzle: Puzzle) -> String {
+    puzzle.data_path("inputs", &format!("{}.txt", puzzle.day()))
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
+pub(crate) fn get_puzzle_path(puzzle: Puzzle) -> String {
+    puzzle.data_path("puzzles", &format!("{}.md", puzzle.day()))
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Creates the directory of a file, as the data folders of other years may not exist yet.
+pub(crate) fn create_parent_dir(path: &str) -> io::Result<()> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
+#[cfg(not(feature = "client"))]
+mod cli {
+    use std::process::{Command, Output, Stdio};
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+    use super::{create_parent_dir, get_input_path, get_puzzle_path, AocCommandError};
+    use crate::template::Puzzle;
+
+    pub fn check() -> Result<(), AocCommandError> {
+        Command::new("aoc")
+            .arg("-V")
+            .output()
+            .map_err(|_| AocCommandError::CommandNotFound)?;
+        Ok(())
     }
-}
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+    pub fn read(puzzle: Puzzle) -> Result<(), AocCommandError> {
+        let puzzle_path = get_puzzle_path(puzzle);
+        create_parent_dir(&puzzle_path)?;
+
+        let args = build_args(
+            "read",
+            &[
+                "--description-only".into(),
+                "--puzzle-file".into(),
+                puzzle_path,
+            ],
+            puzzle,
+        );
+
+        call_aoc_cli(&args)?;
+        Ok(())
+    }
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    pub fn download(puzzle: Puzzle) -> Result<(), AocCommandError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+        create_parent_dir(&input_path)?;
+        create_parent_dir(&puzzle_path)?;
+
+        let args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--input-file".into(),
+                input_path.to_string(),
+                "--puzzle-file".into(),
+                puzzle_path.to_string(),
+            ],
+            puzzle,
+        );
+
+        call_aoc_cli(&args)?;
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    /// Submits an answer and returns the response printed by aoc-cli.
+    pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<String, AocCommandError> {
+        // workaround: the argument order is inverted for submit.
+        let mut args = build_args("submit", &[], puzzle);
+        args.push(part.to_string());
+        args.push(result.to_string());
+
+        // capture stdout so that the response can be inspected.
+        let output = call_aoc_cli_with_stdout(&args, Stdio::piped())?;
+        let response = String::from_utf8_lossy(&output.stdout).to_string();
+        print!("{response}");
+        Ok(response)
+    }
 
-    cmd_args
-}
+    fn build_args(command: &str, args: &[String], puzzle: Puzzle) -> Vec<String> {
+        let mut cmd_args = args.to_vec();
+
+        if let Some(year) = puzzle.year() {
+            cmd_args.push("--year".into());
+            cmd_args.push(year.to_string());
+        }
+
+        cmd_args.append(&mut vec![
+            "--day".into(),
+            puzzle.day().to_string(),
+            command.into(),
+        ]);
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
-
-    if output.status.success() {
-        Ok(output)
-    } else {
-        Err(AocCommandError::BadExitStatus(output))
+        cmd_args
+    }
+
+    fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+        call_aoc_cli_with_stdout(args, Stdio::inherit())
+    }
+
+    fn call_aoc_cli_with_stdout(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
+        // println!("Calling >aoc with: {}", args.join(" "));
+        let output = Command::new("aoc")
+            .args(args)
+            .stdout(stdout)
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(AocCommandError::BadExitStatus(output))
+        }
     }
 }
@@ -0,0 +1,475 @@
+//! Native client for the puzzle site, used instead of aoc-cli when the `client` feature is enabled.
+//!
+//! The session cookie is read from the `AOC_SESSION` environment variable, the file referenced by `AOC_SESSION_FILE`,
+//! or `~/.adventofcode.session` (the same file aoc-cli uses). `AOC_BASE_URL` overrides the address of the site.
+
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use crate::template::aoc_cli::{
+    create_parent_dir, get_input_path, get_puzzle_path, is_correct_answer, AocCommandError,
+};
+use crate::template::{Day, Puzzle, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/plastic041/aoc2024-rs";
+
+pub fn check() -> Result<(), AocCommandError> {
+    AocClient::from_env(Year::from_env()).map(|_| ())
+}
+
+pub fn read(puzzle: Puzzle) -> Result<(), AocCommandError> {
+    let puzzle_path = get_puzzle_path(puzzle);
+    let description = AocClient::from_env(puzzle.year())?.get_puzzle(puzzle.day())?;
+    create_parent_dir(&puzzle_path)?;
+    fs::write(puzzle_path, &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+pub fn download(puzzle: Puzzle) -> Result<(), AocCommandError> {
+    let client = AocClient::from_env(puzzle.year())?;
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    create_parent_dir(&input_path)?;
+    create_parent_dir(&puzzle_path)?;
+    fs::write(&input_path, client.get_input(puzzle.day())?)?;
+    fs::write(&puzzle_path, client.get_puzzle(puzzle.day())?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Submits an answer and returns the response message of the site.
+pub fn submit(puzzle: Puzzle, part: u8, result: &str) -> Result<String, AocCommandError> {
+    let response = AocClient::from_env(puzzle.year())?.submit_answer(puzzle.day(), part, result)?;
+    println!("{response}");
+    Ok(response)
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .redirects(0)
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+        }
+    }
+
+    /// Creates a client for a year, reading the session and base url from the environment.
+    pub fn from_env(year: Option<Year>) -> Result<Self, AocCommandError> {
+        let year = year.map(Year::into_inner).ok_or_else(|| {
+            AocCommandError::MissingConfig(
+                "set AOC_YEAR in `.cargo/config.toml` or pass `--year`.".into(),
+            )
+        })?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &read_session()?, year))
+    }
+
+    pub fn get_input(&self, day: Day) -> Result<String, AocCommandError> {
+        self.get(&format!("/day/{}/input", day.into_inner()))
+    }
+
+    /// Fetches the puzzle description and converts it to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocCommandError> {
+        let html = self.get(&format!("/day/{}", day.into_inner()))?;
+        Ok(html_to_markdown(&html))
+    }
+
+    pub fn submit_answer(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<String, AocCommandError> {
+        let url = self.url(&format!("/day/{}/answer", day.into_inner()));
+
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(map_error)?
+            .into_string()
+            .map_err(|e| AocCommandError::Network(e.to_string()))?;
+
+        let message = html_to_markdown(&html);
+
+        if message.contains("You gave an answer too recently") {
+            return Err(AocCommandError::RateLimited(message));
+        }
+
+        if !is_correct_answer(&message)
+            && !message.contains("not the right answer")
+            && !message.contains("solving the right level")
+        {
+            // the site answers with a login page if the session is invalid.
+            if html.contains("/auth/login") {
+                return Err(AocCommandError::Unauthorised);
+            }
+            return Err(AocCommandError::UnexpectedResponse(message));
+        }
+
+        Ok(message)
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}{path}", self.base_url, self.year)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocCommandError> {
+        self.agent
+            .get(&self.url(path))
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(map_error)?
+            .into_string()
+            .map_err(|e| AocCommandError::Network(e.to_string()))
+    }
+}
+
+fn map_error(e: ureq::Error) -> AocCommandError {
+    match e {
+        ureq::Error::Status(400 | 401 | 403, _) => AocCommandError::Unauthorised,
+        ureq::Error::Status(404, _) => AocCommandError::NotYetUnlocked,
+        ureq::Error::Status(429, response) => AocCommandError::RateLimited(
+            response
+                .into_string()
+                .unwrap_or_else(|_| "too many requests.".into()),
+        ),
+        ureq::Error::Status(code, _) => {
+            AocCommandError::Network(format!("unexpected status code {code}."))
+        }
+        ureq::Error::Transport(e) => AocCommandError::Network(e.to_string()),
+    }
+}
+
+fn read_session() -> Result<String, AocCommandError> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Ok(session);
+        }
+    }
+
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => env::var("HOME")
+            .or_else(|_| env::var("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+            .map_err(|_| {
+                AocCommandError::MissingConfig("could not locate home directory.".into())
+            })?,
+    };
+
+    fs::read_to_string(&path).map_err(|_| {
+        AocCommandError::MissingConfig(format!(
+            "no session cookie found. Set AOC_SESSION or create \"{}\".",
+            path.display()
+        ))
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Converts the `<article>` elements of a page to markdown.
+/// This only handles the handful of tags that are used in puzzle descriptions.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        let article = &rest[start..start + end];
+        let inner = article.find('>').map_or("", |i| &article[i + 1..]);
+        convert_article(inner, &mut out);
+        rest = &rest[start + end + "</article>".len()..];
+    }
+
+    // collapse runs of blank lines.
+    let mut result = String::new();
+    let mut blank_lines = 0;
+
+    for line in out.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    result.trim().to_string() + "\n"
+}
+
+fn convert_article(html: &str, out: &mut String) {
+    let mut in_pre = false;
+    let mut links: Vec<String> = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            out.push_str(&decode_entities(rest));
+            break;
+        };
+
+        out.push_str(&decode_entities(&rest[..tag_start]));
+
+        let Some(tag_end) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_end];
+        rest = &rest[tag_start + tag_end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        // formatting inside of code blocks is dropped to keep the examples intact.
+        if in_pre && name != "pre" {
+            continue;
+        }
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("\n## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("\n```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) => out.push('`'),
+            ("em", _) => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(get_attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!("{name}=\"");
+    let start = tag.find(&pattern)? + pattern.len();
+    let end = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, AocClient};
+    use crate::{day, template::aoc_cli::AocCommandError};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves a single canned response and returns the raw request it received.
+    fn serve_once(status: &str, body: &str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8_lossy(&body));
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, handle) = serve_once("200 OK", "1 2\n3 4\n");
+        let client = AocClient::new(&base_url, "secret\n", 2024);
+
+        assert_eq!(client.get_input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=secret\r\n"));
+    }
+
+    #[test]
+    fn maps_unauthorised() {
+        let (base_url, _) = serve_once("400 Bad Request", "Please log in.");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        let res = client.get_input(day!(1));
+        assert!(matches!(res, Err(AocCommandError::Unauthorised)));
+    }
+
+    #[test]
+    fn maps_not_yet_unlocked() {
+        let (base_url, _) = serve_once("404 Not Found", "Please don't repeatedly request this.");
+        let client = AocClient::new(&base_url, "secret", 2024);
+        let res = client.get_puzzle(day!(25));
+        assert!(matches!(res, Err(AocCommandError::NotYetUnlocked)));
+    }
+
+    #[test]
+    fn maps_network_errors() {
+        let client = AocClient::new("http://127.0.0.1:1", "secret", 2024);
+        let res = client.get_input(day!(1));
+        assert!(matches!(res, Err(AocCommandError::Network(_))));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, handle) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+
+        let response = client.submit_answer(day!(3), 2, "42").unwrap();
+        assert_eq!(
+            response,
+            "That's the right answer! You are *one gold star* closer.\n"
+        );
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn maps_rate_limits() {
+        let (base_url, _) = serve_once(
+            "200 OK",
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+        let res = client.submit_answer(day!(3), 1, "42");
+        assert!(matches!(res, Err(AocCommandError::RateLimited(s)) if s.contains("34s left")));
+    }
+
+    #[test]
+    fn maps_unexpected_responses() {
+        let (base_url, _) = serve_once(
+            "200 OK",
+            "<article><p>Both parts of this puzzle are complete!</p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+        let res = client.submit_answer(day!(3), 1, "42");
+        assert!(
+            matches!(res, Err(AocCommandError::UnexpectedResponse(s)) if s.contains("are complete"))
+        );
+
+        let (base_url, _) = serve_once(
+            "200 OK",
+            r#"<header><a href="/auth/login">[Log In]</a></header><main><p>To play, please identify yourself.</p></main>"#,
+        );
+        let client = AocClient::new(&base_url, "secret", 2024);
+        let res = client.submit_answer(day!(3), 1, "42");
+        assert!(matches!(res, Err(AocCommandError::Unauthorised)));
+    }
+
+    #[test]
+    fn converts_puzzle_html() {
+        let html = r#"<body><article class="day-desc"><h2>--- Day 1: Test ---</h2>
+<p>Some <a href="/2024/about">text</a> &amp; <code>code</code>.</p>
+<pre><code>1 2
+<em>3</em> 4
+</code></pre>
+<ul><li>item <code><em>11</em></code></li></ul>
+</article><p>ignored</p></body>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            [
+                "## --- Day 1: Test ---",
+                "",
+                "Some [text](/2024/about) & `code`.",
+                "",
+                "```",
+                "1 2",
+                "3 4",
+                "```",
+                "",
+                "- item `*11*`",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Configuration of the benchmark loop used by `cargo time`.
+//!
+//! Options can be passed on the command-line of `time` / `solve` (and are forwarded to the solution binaries),
+//! or set via environment variables. Command-line options take precedence over environment variables.
+
+use std::{env, error::Error, fmt::Display, time::Duration};
+
+pub const ENV_WARMUP: &str = "AOC_BENCH_WARMUP";
+pub const ENV_MIN_SAMPLES: &str = "AOC_BENCH_MIN_SAMPLES";
+pub const ENV_MAX_SAMPLES: &str = "AOC_BENCH_MAX_SAMPLES";
+pub const ENV_BUDGET: &str = "AOC_BENCH_BUDGET";
+
+const DEFAULT_WARMUP: u128 = 0;
+const DEFAULT_MIN_SAMPLES: u128 = 10;
+const DEFAULT_MAX_SAMPLES: u128 = 10000;
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+
+/// Benchmark options as passed by the user. Unset values fall back to the environment, then to defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub warmup: Option<u128>,
+    pub min_samples: Option<u128>,
+    pub max_samples: Option<u128>,
+    pub budget: Option<Duration>,
+}
+
+/// Fully resolved benchmark configuration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Number of untimed runs before samples are collected.
+    pub warmup: u128,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Approximate total time to spend on collecting samples.
+    pub budget: Duration,
+}
+
+impl BenchOptions {
+    /// Parses options from command-line arguments.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            warmup: args.opt_value_from_str("--warmup")?,
+            min_samples: args.opt_value_from_str("--min-samples")?,
+            max_samples: args.opt_value_from_str("--max-samples")?,
+            budget: args.opt_value_from_fn("--budget", parse_duration)?,
+        })
+    }
+
+    /// Reads options from the `AOC_BENCH_*` environment variables.
+    pub fn from_env() -> Result<Self, BenchOptionError> {
+        Ok(Self {
+            warmup: read_env(ENV_WARMUP, parse_number)?,
+            min_samples: read_env(ENV_MIN_SAMPLES, parse_number)?,
+            max_samples: read_env(ENV_MAX_SAMPLES, parse_number)?,
+            budget: read_env(ENV_BUDGET, parse_duration)?,
+        })
+    }
+
+    /// Fills unset values from `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            warmup: self.warmup.or(other.warmup),
+            min_samples: self.min_samples.or(other.min_samples),
+            max_samples: self.max_samples.or(other.max_samples),
+            budget: self.budget.or(other.budget),
+        }
+    }
+
+    /// Converts the options back into command-line arguments, used to forward them to child invocations.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(warmup) = self.warmup {
+            args.extend(["--warmup".into(), warmup.to_string()]);
+        }
+        if let Some(min_samples) = self.min_samples {
+            args.extend(["--min-samples".into(), min_samples.to_string()]);
+        }
+        if let Some(max_samples) = self.max_samples {
+            args.extend(["--max-samples".into(), max_samples.to_string()]);
+        }
+        if let Some(budget) = self.budget {
+            args.extend(["--budget".into(), format!("{}ns", budget.as_nanos())]);
+        }
+
+        args
+    }
+
+    /// Applies defaults and validates the resulting configuration.
+    pub fn resolve(&self) -> Result<BenchConfig, BenchOptionError> {
+        let config = BenchConfig {
+            warmup: self.warmup.unwrap_or(DEFAULT_WARMUP),
+            min_samples: self.min_samples.unwrap_or(DEFAULT_MIN_SAMPLES),
+            max_samples: self.max_samples.unwrap_or(DEFAULT_MAX_SAMPLES),
+            budget: self.budget.unwrap_or(DEFAULT_BUDGET),
+        };
+
+        if config.min_samples == 0 || config.min_samples > config.max_samples {
+            return Err(BenchOptionError::InvalidSampleRange(
+                config.min_samples,
+                config.max_samples,
+            ));
+        }
+
+        Ok(config)
+    }
+}
+
+impl BenchConfig {
+    /// Number of samples to collect, based on the duration of a first run.
+    pub fn iterations(&self, base_time: &Duration) -> u128 {
+        (self.budget.as_nanos() / base_time.as_nanos().max(10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        // NOTE: the default options always resolve.
+        BenchOptions::default().resolve().unwrap()
+    }
+}
+
+fn read_env<T>(
+    key: &str,
+    parse: impl Fn(&str) -> Result<T, BenchOptionError>,
+) -> Result<Option<T>, BenchOptionError> {
+    match env::var(key) {
+        Ok(value) if !value.trim().is_empty() => parse(value.trim())
+            .map(Some)
+            .map_err(|e| BenchOptionError::InvalidEnv(key.into(), Box::new(e))),
+        _ => Ok(None),
+    }
+}
+
+fn parse_number(s: &str) -> Result<u128, BenchOptionError> {
+    s.parse()
+        .map_err(|_| BenchOptionError::InvalidNumber(s.into()))
+}
+
+/// Parses a duration such as `500ms`, `1.5s`, `250µs` / `250us` or `100ns`. Plain numbers are read as seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, BenchOptionError> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| BenchOptionError::InvalidDuration(s.into()))?;
+
+    let factor = match unit.trim() {
+        "ns" => 1e-9,
+        "us" | "µs" => 1e-6,
+        "ms" => 1e-3,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        _ => return Err(BenchOptionError::InvalidDuration(s.into())),
+    };
+
+    Duration::try_from_secs_f64(value * factor)
+        .map_err(|_| BenchOptionError::InvalidDuration(s.into()))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum BenchOptionError {
+    InvalidNumber(String),
+    InvalidDuration(String),
+    InvalidSampleRange(u128, u128),
+    InvalidEnv(String, Box<BenchOptionError>),
+}
+
+impl Error for BenchOptionError {}
+
+impl Display for BenchOptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BenchOptionError::InvalidNumber(s) => write!(f, "expected a number, got `{s}`"),
+            BenchOptionError::InvalidDuration(s) => {
+                write!(f, "expected a duration like `500ms` or `2s`, got `{s}`")
+            }
+            BenchOptionError::InvalidSampleRange(min, max) => write!(
+                f,
+                "expected 0 < min samples <= max samples, got min {min} and max {max}"
+            ),
+            BenchOptionError::InvalidEnv(key, e) => write!(f, "invalid value for {key}: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, BenchConfig, BenchOptions};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("2s").unwrap(), Duration::from_secs(2));
+        assert_eq!(parse_duration("1.5").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse_duration("250us").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("250µs").unwrap(), Duration::from_micros(250));
+        assert_eq!(parse_duration("100ns").unwrap(), Duration::from_nanos(100));
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("10 parsecs").is_err());
+    }
+
+    #[test]
+    fn resolves_defaults() {
+        let config = BenchOptions::default().resolve().unwrap();
+        assert_eq!(config, BenchConfig::default());
+        assert_eq!(config.warmup, 0);
+        assert_eq!(config.min_samples, 10);
+        assert_eq!(config.max_samples, 10000);
+        assert_eq!(config.budget, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rejects_invalid_sample_ranges() {
+        let options = BenchOptions {
+            min_samples: Some(100),
+            max_samples: Some(10),
+            ..Default::default()
+        };
+        assert!(options.resolve().is_err());
+    }
+
+    #[test]
+    fn prefers_own_values() {
+        let cli = BenchOptions {
+            warmup: Some(5),
+            ..Default::default()
+        };
+        let env = BenchOptions {
+            warmup: Some(1),
+            budget: Some(Duration::from_secs(3)),
+            ..Default::default()
+        };
+        let merged = cli.or(env);
+        assert_eq!(merged.warmup, Some(5));
+        assert_eq!(merged.budget, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn round_trips_args() {
+        let options = BenchOptions {
+            warmup: Some(3),
+            min_samples: Some(20),
+            max_samples: Some(200),
+            budget: Some(Duration::from_millis(250)),
+        };
+        let args = options.to_args();
+        let mut parsed = pico_args::Arguments::from_vec(args.iter().map(Into::into).collect());
+        assert_eq!(BenchOptions::from_args(&mut parsed).unwrap(), options);
+    }
+
+    #[test]
+    fn computes_iterations() {
+        let config = BenchConfig {
+            warmup: 0,
+            min_samples: 10,
+            max_samples: 1000,
+            budget: Duration::from_millis(100),
+        };
+        assert_eq!(config.iterations(&Duration::from_millis(1)), 100);
+        assert_eq!(config.iterations(&Duration::from_secs(1)), 10);
+        assert_eq!(config.iterations(&Duration::from_nanos(1)), 1000);
+    }
+}
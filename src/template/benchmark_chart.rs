@@ -0,0 +1,308 @@
+//! Module that renders the benchmarks as an SVG bar chart.
+//! The output only depends on the timings, so that charts can be compared across runs.
+
+use std::{env, fs, io, path::Path, time::Duration};
+
+use crate::template::timings::{Timing, Timings};
+
+pub const ENV_CHART: &str = "AOC_BENCHMARK_CHART";
+const DEFAULT_CHART_PATH: &str = ".assets/benchmarks.svg";
+
+const MARGIN_LEFT: f64 = 60.0;
+const MARGIN_RIGHT: f64 = 20.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 30.0;
+const PLOT_HEIGHT: f64 = 220.0;
+const BAR_WIDTH: f64 = 10.0;
+const GROUP_GAP: f64 = 10.0;
+const LEGEND_WIDTH: f64 = 60.0;
+
+/// The series of a chart, with their colors.
+const SERIES: [(&str, &str); 3] = [
+    ("Parse", "#9e9e9e"),
+    ("Part 1", "#4e79a7"),
+    ("Part 2", "#f28e2b"),
+];
+
+/// Path the chart is written to, configured by `AOC_BENCHMARK_CHART`.
+pub fn path() -> String {
+    env::var(ENV_CHART).unwrap_or_else(|_| DEFAULT_CHART_PATH.into())
+}
+
+/// Mean times of the parse step and both parts. Steps benched before stats were tracked are skipped.
+fn steps(timing: &Timing) -> [Option<Duration>; 3] {
+    [
+        timing.parse_stats.map(|s| s.mean),
+        timing.part_1_stats.map(|s| s.mean),
+        timing.part_2_stats.map(|s| s.mean),
+    ]
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn log_nanos(duration: Duration) -> f64 {
+    (duration.as_nanos().max(1) as f64).log10()
+}
+
+/// Label of a power of ten nanoseconds, e.g. `10µs`.
+#[allow(clippy::cast_sign_loss)]
+fn format_decade(exponent: i32) -> String {
+    if exponent < 0 {
+        return format!("{}ns", 10_f64.powi(exponent));
+    }
+
+    const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+    let unit = exponent.div_euclid(3).clamp(0, 3);
+    let value = 10_u64.pow((exponent - unit * 3).max(0) as u32);
+    format!("{value}{}", UNITS[unit as usize])
+}
+
+/// Renders a bar chart of the timings on a log scale, with one group of bars per day.
+pub fn render(timings: &Timings) -> String {
+    let days: Vec<(&Timing, [Option<Duration>; 3])> = timings
+        .data
+        .iter()
+        .map(|timing| (timing, steps(timing)))
+        .filter(|(_, steps)| steps.iter().any(Option::is_some))
+        .collect();
+
+    let logs: Vec<f64> = days
+        .iter()
+        .flat_map(|(_, steps)| steps.iter().flatten().map(|d| log_nanos(*d)))
+        .collect();
+
+    // NOTE: the axis starts one decade below the shortest time, so that a time that is an exact power of ten still gets a visible bar.
+    #[allow(clippy::cast_possible_truncation)]
+    let min_exponent = logs.iter().copied().fold(f64::INFINITY, f64::min).ceil() as i32 - 1;
+    #[allow(clippy::cast_possible_truncation)]
+    let max_exponent = logs
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max)
+        .ceil() as i32;
+    let (min_exponent, max_exponent) = if logs.is_empty() {
+        (0, 1)
+    } else {
+        (min_exponent, max_exponent.max(min_exponent + 1))
+    };
+
+    let plot_bottom = MARGIN_TOP + PLOT_HEIGHT;
+    let y_for = |log: f64| {
+        plot_bottom
+            - (log - f64::from(min_exponent)) / f64::from(max_exponent - min_exponent) * PLOT_HEIGHT
+    };
+
+    let group_width = BAR_WIDTH * SERIES.len() as f64 + GROUP_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let plot_width = (group_width * days.len() as f64).max(LEGEND_WIDTH * SERIES.len() as f64);
+    let width = MARGIN_LEFT + plot_width + MARGIN_RIGHT;
+    let height = plot_bottom + MARGIN_BOTTOM;
+
+    let mut svg = String::new();
+    let mut line = |s: String| {
+        svg.push_str(&s);
+        svg.push('\n');
+    };
+
+    line(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" viewBox="0 0 {width:.0} {height:.0}" font-family="sans-serif" font-size="10">"#
+    ));
+    line(format!(
+        r##"<rect width="{width:.0}" height="{height:.0}" fill="#ffffff"/>"##
+    ));
+
+    // legend
+    let mut legend_x = MARGIN_LEFT;
+    for (name, color) in SERIES {
+        line(format!(
+            r#"<rect x="{legend_x:.1}" y="12" width="10" height="10" fill="{color}"/>"#
+        ));
+        line(format!(
+            r#"<text x="{:.1}" y="21">{name}</text>"#,
+            legend_x + 14.0
+        ));
+        legend_x += LEGEND_WIDTH;
+    }
+
+    // grid lines, one per power of ten
+    for exponent in min_exponent..=max_exponent {
+        let y = y_for(f64::from(exponent));
+        line(format!(
+            r##"<line x1="{MARGIN_LEFT:.1}" y1="{y:.1}" x2="{:.1}" y2="{y:.1}" stroke="#e0e0e0"/>"##,
+            MARGIN_LEFT + plot_width
+        ));
+        line(format!(
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"#,
+            MARGIN_LEFT - 6.0,
+            y + 3.0,
+            format_decade(exponent)
+        ));
+    }
+
+    // bars
+    for (index, (timing, steps)) in days.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let group_x = MARGIN_LEFT + group_width * index as f64 + GROUP_GAP / 2.0;
+
+        for (series, step) in steps.iter().enumerate() {
+            let Some(duration) = step else {
+                continue;
+            };
+
+            let (name, color) = SERIES[series];
+            #[allow(clippy::cast_precision_loss)]
+            let x = group_x + BAR_WIDTH * series as f64;
+            let y = y_for(log_nanos(*duration));
+
+            line(format!(
+                r#"<rect x="{x:.1}" y="{y:.1}" width="{BAR_WIDTH:.1}" height="{:.1}" fill="{color}"><title>Day {} / {name}: {duration:.1?}</title></rect>"#,
+                plot_bottom - y,
+                timing.day
+            ));
+        }
+
+        line(format!(
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            group_x + BAR_WIDTH * SERIES.len() as f64 / 2.0,
+            plot_bottom + 16.0,
+            timing.day.into_inner()
+        ));
+    }
+
+    line(format!(
+        r##"<line x1="{MARGIN_LEFT:.1}" y1="{plot_bottom:.1}" x2="{:.1}" y2="{plot_bottom:.1}" stroke="#616161"/>"##,
+        MARGIN_LEFT + plot_width
+    ));
+    line("</svg>".into());
+
+    svg
+}
+
+/// Writes the chart of the timings to `path`.
+pub fn update(timings: &Timings, path: &str) -> io::Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, render(timings))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_decade, render};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn stats(nanos: u64) -> Option<Stats> {
+        Stats::from_samples(&[Duration::from_nanos(nanos)])
+    }
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    part_1: Some("50.0ns".into()),
+                    part_2: Some("2.0µs".into()),
+                    part_1_stats: stats(50),
+                    part_2_stats: stats(2000),
+                    total_nanos: 2050.0,
+                    ..Timing::new(day!(1), "default")
+                },
+                Timing {
+                    parse: Some("1.0ms".into()),
+                    part_1: Some("10.0ms".into()),
+                    parse_stats: stats(1_000_000),
+                    part_1_stats: stats(10_000_000),
+                    total_nanos: 11_000_000.0,
+                    ..Timing::new(day!(3), "default")
+                },
+                Timing {
+                    part_1: Some("1ms".into()),
+                    total_nanos: 1_000_000.0,
+                    ..Timing::new(day!(4), "default")
+                },
+            ],
+            history: vec![],
+            profiles: vec![],
+        }
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(-1), "0.1ns");
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(4), "10µs");
+        assert_eq!(format_decade(8), "100ms");
+        assert_eq!(format_decade(10), "10s");
+    }
+
+    #[test]
+    fn renders_chart() {
+        let expected = [
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="260" height="290" viewBox="0 0 260 290" font-family="sans-serif" font-size="10">"#,
+            r##"<rect width="260" height="290" fill="#ffffff"/>"##,
+            r##"<rect x="60.0" y="12" width="10" height="10" fill="#9e9e9e"/>"##,
+            r#"<text x="74.0" y="21">Parse</text>"#,
+            r##"<rect x="120.0" y="12" width="10" height="10" fill="#4e79a7"/>"##,
+            r#"<text x="134.0" y="21">Part 1</text>"#,
+            r##"<rect x="180.0" y="12" width="10" height="10" fill="#f28e2b"/>"##,
+            r#"<text x="194.0" y="21">Part 2</text>"#,
+            r##"<line x1="60.0" y1="260.0" x2="240.0" y2="260.0" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="263.0" text-anchor="end">10ns</text>"#,
+            r##"<line x1="60.0" y1="223.3" x2="240.0" y2="223.3" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="226.3" text-anchor="end">100ns</text>"#,
+            r##"<line x1="60.0" y1="186.7" x2="240.0" y2="186.7" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="189.7" text-anchor="end">1µs</text>"#,
+            r##"<line x1="60.0" y1="150.0" x2="240.0" y2="150.0" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="153.0" text-anchor="end">10µs</text>"#,
+            r##"<line x1="60.0" y1="113.3" x2="240.0" y2="113.3" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="116.3" text-anchor="end">100µs</text>"#,
+            r##"<line x1="60.0" y1="76.7" x2="240.0" y2="76.7" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="79.7" text-anchor="end">1ms</text>"#,
+            r##"<line x1="60.0" y1="40.0" x2="240.0" y2="40.0" stroke="#e0e0e0"/>"##,
+            r#"<text x="54.0" y="43.0" text-anchor="end">10ms</text>"#,
+            r##"<rect x="75.0" y="234.4" width="10.0" height="25.6" fill="#4e79a7"><title>Day 01 / Part 1: 50.0ns</title></rect>"##,
+            r##"<rect x="85.0" y="175.6" width="10.0" height="84.4" fill="#f28e2b"><title>Day 01 / Part 2: 2.0µs</title></rect>"##,
+            r#"<text x="80.0" y="276.0" text-anchor="middle">1</text>"#,
+            r##"<rect x="105.0" y="76.7" width="10.0" height="183.3" fill="#9e9e9e"><title>Day 03 / Parse: 1.0ms</title></rect>"##,
+            r##"<rect x="115.0" y="40.0" width="10.0" height="220.0" fill="#4e79a7"><title>Day 03 / Part 1: 10.0ms</title></rect>"##,
+            r#"<text x="120.0" y="276.0" text-anchor="middle">3</text>"#,
+            r##"<line x1="60.0" y1="260.0" x2="240.0" y2="260.0" stroke="#616161"/>"##,
+            "</svg>",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&get_mock_timings()), expected);
+
+        let exact_decade = Timings {
+            data: vec![Timing {
+                part_1: Some("1.0µs".into()),
+                part_1_stats: stats(1000),
+                total_nanos: 1000.0,
+                ..Timing::new(day!(2), "default")
+            }],
+            ..Timings::default()
+        };
+        let svg = render(&exact_decade);
+        assert!(svg.contains(r#"text-anchor="end">100ns</text>"#));
+        assert!(svg.contains(
+            r##"<rect x="75.0" y="40.0" width="10.0" height="220.0" fill="#4e79a7"><title>Day 02 / Part 1: 1.0µs</title></rect>"##
+        ));
+    }
+
+    #[test]
+    fn renders_empty_timings() {
+        let svg = render(&Timings::default());
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(!svg.contains("<title>"));
+    }
+}
@@ -1,5 +1,21 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::run_multi::{run_multi, BuildProfile};
+use crate::template::{all_days, bench::BenchOptions, Timeouts, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    year: Option<Year>,
+    is_release: bool,
+    dhat: bool,
+    count_allocs: bool,
+    jobs: usize,
+    timeouts: Timeouts,
+) {
+    run_multi(
+        &all_days().collect(),
+        year,
+        BuildProfile::new(is_release, dhat, count_allocs),
+        false,
+        jobs,
+        timeouts,
+        &BenchOptions::default(),
+    );
 }
@@ -0,0 +1,65 @@
+use std::process;
+
+use crate::template::answers::{AnswerCheck, Answers};
+use crate::template::run_multi::{run_multi, BuildProfile, DayStatus};
+use crate::template::{all_days, bench::BenchOptions, Timeouts, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(year: Option<Year>, is_release: bool, jobs: usize, timeouts: Timeouts) {
+    let answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read the stored answers: {e}");
+        process::exit(1);
+    });
+
+    let run = run_multi(
+        &all_days().collect(),
+        year,
+        BuildProfile::new(is_release, false, false),
+        false,
+        jobs,
+        timeouts,
+        &BenchOptions::default(),
+    );
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+
+    println!("\n{ANSI_BOLD}Answers{ANSI_RESET}");
+    println!("-------");
+
+    for day_run in &run.days {
+        // skip days that are neither solved nor recorded.
+        if day_run.reports.is_empty()
+            && (1..=2).all(|part| answers.get(day_run.day, part).is_none())
+        {
+            continue;
+        }
+
+        for part in 1..=2 {
+            let label = format!("Day {} / Part {part}", day_run.day);
+
+            match answers.check(day_run.day, part, day_run.answer(part)) {
+                AnswerCheck::Pass => {
+                    passed += 1;
+                    println!("{label}: ✔");
+                }
+                AnswerCheck::Fail { expected, actual } => {
+                    failed += 1;
+                    let actual = match day_run.status {
+                        DayStatus::BuildFailed => "a build failure".into(),
+                        _ => actual.unwrap_or_else(|| "✖".into()),
+                    };
+                    println!("{label}: ✖ expected {expected}, got {actual}");
+                }
+                AnswerCheck::Missing => {
+                    missing += 1;
+                    println!("{label}: ? no stored answer");
+                }
+            }
+        }
+    }
+
+    println!("\n{ANSI_BOLD}{passed} passed, {failed} failed, {missing} missing.{ANSI_RESET}");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
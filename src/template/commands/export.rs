@@ -0,0 +1,49 @@
+use std::{fs, process};
+
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::create_parent_dir;
+use crate::template::export::{collect, render, ExportFormat};
+use crate::template::run_multi::{run_multi, BuildProfile};
+use crate::template::{all_days, bench::BenchOptions, Timeouts, Year};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<Year>,
+    format: ExportFormat,
+    path: &str,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    timeouts: Timeouts,
+    bench: &BenchOptions,
+) {
+    let answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read the stored answers: {e}");
+        process::exit(1);
+    });
+
+    let profile = if is_timed {
+        BuildProfile::Release
+    } else {
+        BuildProfile::new(is_release, false, false)
+    };
+
+    let run = run_multi(
+        &all_days().collect(),
+        year,
+        profile,
+        is_timed,
+        jobs,
+        timeouts,
+        bench,
+    );
+
+    let rows = collect(&run, &answers);
+
+    if let Err(e) = create_parent_dir(path).and_then(|()| fs::write(path, render(&rows, format))) {
+        eprintln!("Failed to write export to \"{path}\": {e}");
+        process::exit(1);
+    }
+
+    println!("\nExported {} results to \"{path}\".", rows.len());
+}
@@ -0,0 +1,100 @@
+use std::{fs, process};
+
+use crate::template::aoc_cli::get_puzzle_path;
+use crate::template::extract::{extract, fill_assertions, Extracted};
+use crate::template::Puzzle;
+
+/// Write the examples of the downloaded puzzle description to the example files, and fill the test assertions of the solution.
+///
+/// Example files that are not empty are kept, unless `overwrite` is set.
+pub fn handle(puzzle: Puzzle, overwrite: bool) {
+    let day = puzzle.day();
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!("Puzzle description \"{puzzle_path}\" not found, download it with `cargo download {day}` first.");
+        process::exit(1);
+    };
+
+    let mut extracted = extract(&markdown);
+
+    let example_path = puzzle.data_path("examples", &format!("{day}.txt"));
+    let example_2_path = puzzle.data_path("examples", &format!("{day}-2.txt"));
+
+    let example_matches = write_example(&example_path, extracted.example.as_deref(), overwrite);
+    let example_2_matches = match extracted.example_2.as_deref() {
+        Some(example) => write_example(&example_2_path, Some(example), overwrite),
+        None => example_matches,
+    };
+
+    if extracted.example.is_none() {
+        println!("No example found.");
+    }
+
+    for (part, matches) in [(1, example_matches), (2, example_2_matches)] {
+        match extracted.answer(part) {
+            Some(answer) => println!(
+                "Part {part}: guessed expected answer `{answer}`, check it against the puzzle description."
+            ),
+            None => println!("Part {part}: no expected answer found."),
+        }
+
+        // NOTE: the answer only belongs to the example file if it holds the extracted example.
+        if !matches {
+            match part {
+                1 => extracted.answer_1 = None,
+                _ => extracted.answer_2 = None,
+            }
+        }
+    }
+
+    fill_solution(&puzzle.bin_path(), &extracted);
+}
+
+/// Write an example to a file. Returns `true` if the file holds the example afterwards.
+fn write_example(path: &str, example: Option<&str>, overwrite: bool) -> bool {
+    let Some(example) = example else {
+        return false;
+    };
+
+    let existing = fs::read_to_string(path).unwrap_or_default();
+
+    if existing == example {
+        return true;
+    }
+
+    if !existing.trim().is_empty() && !overwrite {
+        println!("Kept existing example file \"{path}\", pass `--overwrite` to replace it.");
+        return false;
+    }
+
+    match fs::write(path, example) {
+        Ok(()) => {
+            println!("Wrote example to \"{path}\".");
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write example file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn fill_solution(module_path: &str, extracted: &Extracted) {
+    let Ok(source) = fs::read_to_string(module_path) else {
+        return;
+    };
+
+    let filled = fill_assertions(&source, extracted);
+    if filled == source {
+        return;
+    }
+
+    match fs::write(module_path, filled) {
+        Ok(()) => println!("Filled test assertions in \"{module_path}\"."),
+        Err(e) => {
+            eprintln!("Failed to update module file: {e}");
+            process::exit(1);
+        }
+    }
+}
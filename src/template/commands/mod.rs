@@ -1,6 +1,10 @@
 pub mod all;
+pub mod check_answers;
 pub mod download;
+pub mod export;
+pub mod extract_examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod watch;
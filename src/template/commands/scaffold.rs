@@ -1,13 +1,12 @@
 use std::{
     fs::{File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::templates::render_template;
+use crate::template::Puzzle;
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -20,6 +19,9 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +29,19 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, template: &str, overwrite: bool) {
+    let day = puzzle.day();
+    let input_path = puzzle.data_path("inputs", &format!("{day}.txt"));
+    let example_path = puzzle.data_path("examples", &format!("{day}.txt"));
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    let contents = match render_template(template, puzzle) {
+        Ok(contents) => contents,
+        Err(e) => {
+            eprintln!("Failed to read template: {e}");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +51,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -75,5 +82,9 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    let year_arg = puzzle
+        .explicit_year()
+        .map(|year| format!(" --year {year}"))
+        .unwrap_or_default();
+    println!("🎄 Type `cargo solve {day}{year_arg}` to run your solution.");
 }
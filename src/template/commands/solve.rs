@@ -1,20 +1,24 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
-
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
-
-    if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
-    }
+use crate::template::{bench::BenchOptions, run_multi::BuildProfile, Puzzle};
+
+pub fn handle(
+    puzzle: Puzzle,
+    release: bool,
+    dhat: bool,
+    count_allocs: bool,
+    submit_part: Option<u8>,
+    time: bool,
+    bench: &BenchOptions,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
+
+    cmd_args.extend(
+        BuildProfile::new(release, dhat, count_allocs)
+            .cargo_args()
+            .iter()
+            .map(ToString::to_string),
+    );
 
     cmd_args.push("--".to_string());
 
@@ -23,6 +27,12 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if time {
+        cmd_args.push("--time".to_string());
+    }
+
+    cmd_args.extend(bench.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,40 +1,174 @@
 use std::collections::HashSet;
+use std::process::{self, Command};
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::bench::BenchOptions;
+use crate::template::machine::MachineProfile;
+use crate::template::run_multi::{run_multi, BuildProfile};
+use crate::template::timings::{TimingChange, Timings};
+use crate::template::{
+    all_days, benchmark_chart, readme_benchmarks, unix_timestamp, Day, Timeouts, Year, ANSI_BOLD,
+    ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<f64>,
+    profile: Option<String>,
+    dhat: bool,
+    count_allocs: bool,
+    timeouts: Timeouts,
+    bench: &BenchOptions,
+) {
+    // measuring the allocations slows them down, so only the heap usage of such a run is worth keeping.
+    let measures_memory = dhat || count_allocs;
+
+    if measures_memory && compare.is_some() {
+        eprintln!("`--compare` can not be combined with `--dhat` or `--count-allocs`, as measuring the allocations distorts the timings.");
+        process::exit(1);
+    }
+
+    let profile = MachineProfile::detect(&MachineProfile::selected_name(profile));
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read the stored timings: {e}");
+        process::exit(1);
+    });
+    let profile_timings = stored_timings.for_profile(&profile.name);
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || compare.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| !profile_timings.is_day_complete(*day))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(
+        &days_to_run,
+        year,
+        // the dhat and count-allocs builds are optimized as well, and measure the heap usage of each part.
+        BuildProfile::new(true, dhat, count_allocs),
+        true,
+        1,
+        timeouts,
+        bench,
+    )
+    .timings
+    .unwrap();
+
+    println!("\n{ANSI_BOLD}Profile:{ANSI_RESET} {profile}");
+    let profile_name = profile.name.clone();
+    timings.assign_profile(profile);
+
+    let has_regressions = compare
+        .is_some_and(|threshold| print_comparison(&profile_timings.compare(&timings), threshold));
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = if measures_memory {
+            println!(
+                "\nOnly the heap usage is stored, the timings measured with it are discarded."
+            );
+            stored_timings.merge_memory(&timings)
+        } else {
+            timings.record_history(unix_timestamp(), current_commit());
+            stored_timings.merge(&timings)
+        };
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+
+        // the readme only lists the benchmarks of the configured year.
+        if year.is_some() {
+            println!("Stored updated benchmarks.");
+        } else {
+            match readme_benchmarks::update(&merged_timings) {
+                Ok(()) => {
+                    println!("Stored updated benchmarks.");
+                }
+                Err(_) => {
+                    eprintln!("Failed to store updated benchmarks.");
+                }
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+
+            // the chart shows the reference profile if there is one, the benched profile otherwise.
+            let chart_profile = MachineProfile::reference_name().unwrap_or(profile_name);
+            let chart_path = benchmark_chart::path();
+            match benchmark_chart::update(&merged_timings.for_profile(&chart_profile), &chart_path)
+            {
+                Ok(()) => {
+                    println!("Stored benchmark chart at \"{chart_path}\".");
+                }
+                Err(e) => {
+                    eprintln!("Failed to store benchmark chart: {e}");
+                }
             }
         }
     }
+
+    if has_regressions {
+        process::exit(1);
+    }
+}
+
+/// Print the changes against the stored timings. Returns whether any step regressed.
+fn print_comparison(changes: &[TimingChange], threshold: f64) -> bool {
+    println!("\n{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("----------");
+
+    if changes.is_empty() {
+        println!("No stored timings to compare with.");
+        return false;
+    }
+
+    let mut regressions = 0;
+
+    for change in changes {
+        let TimingChange {
+            day,
+            step,
+            before,
+            after,
+        } = change;
+
+        let percent = change.relative() * 100.0;
+        let marker = if change.is_regression(threshold) {
+            regressions += 1;
+            " ⚠ regression"
+        } else {
+            ""
+        };
+
+        println!("Day {day} / {step}: {before:.1?} → {after:.1?} ({percent:+.1}%){marker}");
+    }
+
+    println!(
+        "\n{ANSI_BOLD}{regressions} regressions above {:.0}%.{ANSI_RESET}",
+        threshold * 100.0
+    );
+
+    regressions > 0
+}
+
+/// Short hash of the checked out commit, if run inside a git repository.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8(output.stdout).ok()?;
+    Some(commit.trim().to_string())
 }
@@ -0,0 +1,143 @@
+use std::{
+    env, fs,
+    process::{self, Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::template::report::{PartReport, ENV_REPORT_FILE};
+use crate::template::watch::{diff_answers, Snapshot, TestSummary};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Re-run the example tests and the real input of a puzzle whenever one of its files changes.
+pub fn handle(puzzle: Puzzle, release: bool, interval: Duration) {
+    let mut snapshot = Snapshot::take(puzzle);
+    let mut previous: Option<Vec<PartReport>> = None;
+
+    println!("Watching {puzzle}, press Ctrl-C to stop.");
+
+    loop {
+        println!();
+        println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}");
+        println!("------");
+
+        if let Some(reports) = run(puzzle, release, previous.as_deref()) {
+            previous = Some(reports);
+        }
+
+        println!("\n{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+
+        loop {
+            thread::sleep(interval);
+
+            let current = Snapshot::take(puzzle);
+            let changes: Vec<String> = current
+                .changes(&snapshot)
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+
+            if !changes.is_empty() {
+                println!("Changed: {}", changes.join(", "));
+                snapshot = current;
+                break;
+            }
+        }
+    }
+}
+
+/// Run the tests, then the real input. Returns the reports of the real input, if the solution ran.
+fn run(puzzle: Puzzle, release: bool, previous: Option<&[PartReport]>) -> Option<Vec<PartReport>> {
+    let Some(tests) = run_tests(puzzle, release) else {
+        println!("Tests: ✖ build failed");
+        return None;
+    };
+
+    println!("Tests: {tests}");
+
+    let reports = run_solution(puzzle, release)?;
+
+    for line in diff_answers(previous, &reports) {
+        println!("{line}");
+    }
+
+    Some(reports)
+}
+
+/// Run `cargo test` for the solution. Compiler errors and the panic messages of failed tests are printed.
+fn run_tests(puzzle: Puzzle, release: bool) -> Option<TestSummary> {
+    let mut args = vec!["test", "--quiet", "--bin"];
+    let bin = puzzle.to_string();
+    args.push(&bin);
+    if release {
+        args.push("--release");
+    }
+
+    // NOTE: backtraces would bury the panic messages of failed tests.
+    let output = match Command::new("cargo")
+        .args(&args)
+        .env("RUST_BACKTRACE", "0")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to call cargo: {e}");
+            process::exit(1);
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let summary = TestSummary::parse(&stdout);
+
+    match &summary {
+        // the build failed, show the compiler errors.
+        None => eprint!("{}", String::from_utf8_lossy(&output.stderr)),
+        Some(summary) => {
+            for failure in &summary.failures {
+                println!("{}", failure.message);
+            }
+        }
+    }
+
+    summary
+}
+
+/// Run the solution against the real input and collect its reports. The output of the solution is not shown.
+fn run_solution(puzzle: Puzzle, release: bool) -> Option<Vec<PartReport>> {
+    let report_path = env::temp_dir().join(format!("aoc-watch-{}-{puzzle}.jsonl", process::id()));
+    // NOTE: the runner appends to the report file, make sure that we start from scratch.
+    let _ = fs::remove_file(&report_path);
+
+    let mut args = vec!["run", "--quiet", "--bin"];
+    let bin = puzzle.to_string();
+    args.push(&bin);
+    if release {
+        args.push("--release");
+    }
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .env(ENV_REPORT_FILE, &report_path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .status();
+
+    let reports: Vec<PartReport> = fs::read_to_string(&report_path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.parse().ok())
+        .collect();
+    let _ = fs::remove_file(&report_path);
+
+    match status {
+        Ok(status) if status.success() => Some(reports),
+        Ok(_) => {
+            println!("Real input: ✖ exited with an error");
+            None
+        }
+        Err(e) => {
+            eprintln!("Failed to call cargo: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Named examples with expected answers, read from `data/examples/<day>.json`.
+//!
+//! The [`examples!`](crate::examples) macro generates a test per example.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Answer, Day, Puzzle};
+
+/// Where the input of an example comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExampleInput {
+    /// A file in the examples folder, e.g. `01-2.txt`.
+    File(String),
+    /// The input itself.
+    Inline(String),
+}
+
+/// A single example of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: ExampleInput,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// Parameters that differ from the real input, e.g. the size of a grid.
+    pub params: BTreeMap<String, String>,
+}
+
+impl Example {
+    /// Parse the examples of a day. Examples without `file` or `input` read `<day>.txt`.
+    pub fn parse_all(json: &str, day: Day) -> Result<Vec<Self>, String> {
+        let json = JsonValue::from_str(json).or(Err("not valid JSON file."))?;
+
+        json.get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?
+            .iter()
+            .map(|example| Self::parse(example, day))
+            .collect()
+    }
+
+    fn parse(value: &JsonValue, day: Day) -> Result<Self, String> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.name to be a string.")?
+            .clone();
+
+        let string = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(value) => scalar_to_string(value).map(Some).ok_or(format!(
+                "Expected example.{key} of `{name}` to be a string or number."
+            )),
+        };
+
+        let input = match (string("file")?, string("input")?) {
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "Expected example `{name}` to have either a file or an input."
+                ))
+            }
+            (Some(file), None) => ExampleInput::File(file),
+            (None, Some(input)) => ExampleInput::Inline(input),
+            (None, None) => ExampleInput::File(format!("{day}.txt")),
+        };
+
+        let params = match json.get("params") {
+            None => BTreeMap::new(),
+            Some(params) => params
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!(
+                    "Expected example.params of `{name}` to be an object."
+                ))?
+                .iter()
+                .map(|(key, value)| {
+                    scalar_to_string(value)
+                        .map(|v| (key.clone(), v))
+                        .ok_or(format!(
+                            "Expected example.params.{key} of `{name}` to be a string or number."
+                        ))
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        let part_1 = string("part_1")?;
+        let part_2 = string("part_2")?;
+
+        Ok(Example {
+            name,
+            input,
+            part_1,
+            part_2,
+            params,
+        })
+    }
+
+    /// The input of the example. Panics if its file can not be read.
+    pub fn read_input(&self, puzzle: Puzzle) -> String {
+        match &self.input {
+            ExampleInput::Inline(input) => input.clone(),
+            ExampleInput::File(file) => {
+                let path = puzzle.data_path("examples", file);
+                fs::read_to_string(&path)
+                    .unwrap_or_else(|e| panic!("could not open example file \"{path}\": {e}"))
+            }
+        }
+    }
+
+    /// The expected answer of a part, if known.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// A parameter of the example, parsed to `T`. Panics if it can not be parsed.
+    pub fn param<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.params.get(key).map(|value| {
+            value.parse().unwrap_or_else(|_| {
+                panic!("could not parse param `{key}` of example `{}`", self.name)
+            })
+        })
+    }
+
+    /// Run a part and compare its answer with the expected one. Parts without an expected answer are not run.
+    pub fn check<T: Into<Answer>>(&self, part: u8, solve: impl FnOnce() -> Option<T>) {
+        let Some(expected) = self.expected(part) else {
+            return;
+        };
+
+        let actual = solve().map(|answer| answer.into().canonical());
+        assert_eq!(
+            actual.as_deref(),
+            Some(expected),
+            "example `{}`, part {part}",
+            self.name
+        );
+    }
+}
+
+/// Read a scalar as string. Numbers without a fraction are written without a decimal point.
+fn scalar_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(s) => Some(s.clone()),
+        #[allow(clippy::cast_possible_truncation)]
+        JsonValue::Number(n) if n.fract() == 0.0 => Some((*n as i64).to_string()),
+        JsonValue::Number(n) => Some(n.to_string()),
+        JsonValue::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Read the examples of a puzzle from `data/examples/<day>.json`. Panics if the file is missing or malformed.
+pub fn read_examples(puzzle: impl Into<Puzzle>) -> Vec<Example> {
+    let puzzle = puzzle.into();
+    let path = puzzle.data_path("examples", &format!("{}.json", puzzle.day()));
+
+    fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| Example::parse_all(&json, puzzle.day()))
+        .unwrap_or_else(|e| panic!("could not read examples from \"{path}\": {e}"))
+}
+
+/// Returns the example with the given name. Panics if there is none.
+pub fn example(puzzle: impl Into<Puzzle>, name: &str) -> Example {
+    let puzzle = puzzle.into();
+    read_examples(puzzle)
+        .into_iter()
+        .find(|example| example.name == name)
+        .unwrap_or_else(|| panic!("no example named `{name}` for {puzzle}"))
+}
+
+/// Asserts that every example of a puzzle is listed in `names`, i.e. has a test.
+pub fn assert_covered(puzzle: impl Into<Puzzle>, names: &[&str]) {
+    let missing: Vec<String> = read_examples(puzzle)
+        .into_iter()
+        .map(|example| example.name)
+        .filter(|name| !names.contains(&name.as_str()))
+        .collect();
+
+    assert!(
+        missing.is_empty(),
+        "examples without tests: {}",
+        missing.join(", ")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, ExampleInput};
+    use crate::day;
+
+    #[test]
+    fn parses_examples() {
+        let json = r#"{
+            "examples": [
+                { "name": "small", "part_1": 140, "part_2": "80" },
+                { "name": "large", "file": "12-2.txt", "part_1": 1930, "params": { "size": 6, "label": "x" } },
+                { "name": "inline", "input": "AB\nCD", "part_2": null }
+            ]
+        }"#;
+
+        let examples = Example::parse_all(json, day!(12)).unwrap();
+        assert_eq!(examples.len(), 3);
+
+        assert_eq!(examples[0].input, ExampleInput::File("12.txt".into()));
+        assert_eq!(examples[0].expected(1), Some("140"));
+        assert_eq!(examples[0].expected(2), Some("80"));
+
+        assert_eq!(examples[1].input, ExampleInput::File("12-2.txt".into()));
+        assert_eq!(examples[1].expected(2), None);
+        assert_eq!(examples[1].param::<usize>("size"), Some(6));
+        assert_eq!(examples[1].param::<String>("label"), Some("x".into()));
+        assert_eq!(examples[1].param::<usize>("missing"), None);
+
+        assert_eq!(examples[2].input, ExampleInput::Inline("AB\nCD".into()));
+        assert_eq!(examples[2].expected(1), None);
+    }
+
+    #[test]
+    fn errors_for_malformed_examples() {
+        let parse = |json: &str| Example::parse_all(json, day!(1));
+
+        assert!(parse(r#"{ "data": [] }"#).is_err());
+        assert!(parse(r#"{ "examples": [{ "part_1": 1 }] }"#).is_err());
+        assert!(
+            parse(r#"{ "examples": [{ "name": "a", "file": "01.txt", "input": "" }] }"#).is_err()
+        );
+        assert!(parse(r#"{ "examples": [{ "name": "a", "params": { "size": [1] } }] }"#).is_err());
+    }
+
+    #[test]
+    fn checks_answers() {
+        let example = Example::parse_all(
+            r#"{ "examples": [{ "name": "a", "input": "", "part_1": 11 }] }"#,
+            day!(1),
+        )
+        .unwrap()
+        .remove(0);
+
+        example.check(1, || Some(11));
+        // parts without an expected answer are not run.
+        example.check(2, || -> Option<u32> { panic!("should not run") });
+    }
+
+    #[test]
+    #[should_panic(expected = "example `a`, part 1")]
+    fn panics_for_wrong_answers() {
+        let example = Example::parse_all(
+            r#"{ "examples": [{ "name": "a", "input": "", "part_1": 11 }] }"#,
+            day!(1),
+        )
+        .unwrap()
+        .remove(0);
+
+        example.check(1, || Some(12));
+    }
+}
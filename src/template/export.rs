@@ -0,0 +1,449 @@
+//! Exports the results of a run in formats that CI tools understand.
+
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::answers::{AnswerCheck, Answers};
+use crate::template::report::PARSE_PART;
+use crate::template::run_multi::{DayStatus, MultiRun};
+use crate::template::Day;
+
+/// A format that results can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+    /// A JUnit XML report with a test suite per day and a test case per part.
+    Junit,
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::JsonLines),
+            "junit" => Ok(Self::Junit),
+            _ => Err(ExportFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`ExportFormat`].
+#[derive(Debug)]
+pub struct ExportFormatFromStrError;
+
+impl Error for ExportFormatFromStrError {}
+
+impl Display for ExportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting one of `csv`, `jsonl` or `junit`")
+    }
+}
+
+/// The result of a single step of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The answer matches the accepted answer, or the parse step finished.
+    Passed,
+    /// The answer differs from the accepted answer.
+    Failed,
+    /// The part returned an answer, but no accepted answer is stored.
+    Unchecked,
+    /// The part returned `None`.
+    Unsolved,
+    /// The step did not report, e.g. because the solution panicked before.
+    NotRun,
+}
+
+impl Outcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Passed => "passed",
+            Self::Failed => "failed",
+            Self::Unchecked => "unchecked",
+            Self::Unsolved => "unsolved",
+            Self::NotRun => "not_run",
+        }
+    }
+}
+
+/// One exported step: the parse step (part `0`) or a part of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportRow {
+    pub day: Day,
+    pub part: u8,
+    pub status: DayStatus,
+    pub outcome: Outcome,
+    pub answer: Option<String>,
+    pub expected: Option<String>,
+    /// Mean time of the step, if it reported.
+    pub duration: Option<Duration>,
+    pub samples: u128,
+}
+
+fn status_str(status: &DayStatus) -> &'static str {
+    match status {
+        DayStatus::Missing => "missing",
+        DayStatus::BuildFailed => "build_failed",
+        DayStatus::Ran => "ran",
+        DayStatus::Panicked => "panicked",
+        DayStatus::TimedOut => "timed_out",
+        DayStatus::FailedToRun(_) => "failed_to_run",
+    }
+}
+
+/// Collects the rows of all scaffolded days of a run, checking answers against `answers`.
+pub fn collect(run: &MultiRun, answers: &Answers) -> Vec<ExportRow> {
+    let mut rows = vec![];
+
+    for day_run in run.days.iter().filter(|d| d.status != DayStatus::Missing) {
+        let parse = day_run.reports.iter().find(|r| r.part == PARSE_PART);
+
+        if let Some(report) = parse {
+            rows.push(ExportRow {
+                day: day_run.day,
+                part: PARSE_PART,
+                status: day_run.status.clone(),
+                outcome: Outcome::Passed,
+                answer: None,
+                expected: None,
+                duration: Some(report.stats.mean),
+                samples: report.stats.samples,
+            });
+        }
+
+        for part in 1..=2 {
+            let report = day_run.reports.iter().find(|r| r.part == part);
+            let answer = report.and_then(|r| r.answer.clone());
+
+            let outcome = match (report, answers.check(day_run.day, part, answer.as_deref())) {
+                (None, _) => Outcome::NotRun,
+                (Some(_), AnswerCheck::Pass) => Outcome::Passed,
+                (Some(_), _) if answer.is_none() => Outcome::Unsolved,
+                (Some(_), AnswerCheck::Fail { .. }) => Outcome::Failed,
+                (Some(_), AnswerCheck::Missing) => Outcome::Unchecked,
+            };
+
+            rows.push(ExportRow {
+                day: day_run.day,
+                part,
+                status: day_run.status.clone(),
+                outcome,
+                answer,
+                expected: answers.get(day_run.day, part).map(Into::into),
+                duration: report.map(|r| r.stats.mean),
+                samples: report.map_or(0, |r| r.stats.samples),
+            });
+        }
+    }
+
+    rows
+}
+
+/// Renders the rows in the given format.
+pub fn render(rows: &[ExportRow], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => to_csv(rows),
+        ExportFormat::JsonLines => to_json_lines(rows),
+        ExportFormat::Junit => to_junit(rows),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv(rows: &[ExportRow]) -> String {
+    let mut lines = vec!["day,part,status,outcome,answer,expected,nanos,samples".to_string()];
+
+    for row in rows {
+        let fields = [
+            row.day.to_string(),
+            row.part.to_string(),
+            status_str(&row.status).into(),
+            row.outcome.as_str().into(),
+            row.answer.clone().unwrap_or_default(),
+            row.expected.clone().unwrap_or_default(),
+            row.duration
+                .map(|d| d.as_nanos().to_string())
+                .unwrap_or_default(),
+            row.samples.to_string(),
+        ];
+
+        lines.push(
+            fields
+                .iter()
+                .map(|f| escape_csv(f))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+
+    lines.join("\n") + "\n"
+}
+
+fn to_json_lines(rows: &[ExportRow]) -> String {
+    let optional_string =
+        |value: &Option<String>| value.clone().map_or(JsonValue::Null, JsonValue::String);
+
+    rows.iter()
+        .map(|row| {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+            map.insert("day".into(), JsonValue::String(row.day.to_string()));
+            map.insert("part".into(), JsonValue::Number(f64::from(row.part)));
+            map.insert(
+                "status".into(),
+                JsonValue::String(status_str(&row.status).into()),
+            );
+            map.insert(
+                "outcome".into(),
+                JsonValue::String(row.outcome.as_str().into()),
+            );
+            map.insert("answer".into(), optional_string(&row.answer));
+            map.insert("expected".into(), optional_string(&row.expected));
+
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                "nanos".into(),
+                row.duration
+                    .map_or(JsonValue::Null, |d| JsonValue::Number(d.as_nanos() as f64)),
+            );
+
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("samples".into(), JsonValue::Number(row.samples as f64));
+
+            // NOTE: serializing a JSON object built from valid values can not fail.
+            JsonValue::Object(map).stringify().unwrap() + "\n"
+        })
+        .collect()
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn to_junit(rows: &[ExportRow]) -> String {
+    let is_failure = |row: &ExportRow| matches!(row.outcome, Outcome::Failed | Outcome::Unsolved);
+    let is_error = |row: &ExportRow| row.outcome == Outcome::NotRun;
+    let seconds = |rows: &[&ExportRow]| {
+        rows.iter()
+            .filter_map(|r| r.duration)
+            .sum::<Duration>()
+            .as_secs_f64()
+    };
+
+    let all: Vec<&ExportRow> = rows.iter().collect();
+    let mut days: Vec<Day> = rows.iter().map(|r| r.day).collect();
+    days.dedup();
+
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(
+            r#"<testsuites name="advent_of_code" tests="{}" failures="{}" errors="{}" time="{:.9}">"#,
+            all.len(),
+            all.iter().filter(|r| is_failure(r)).count(),
+            all.iter().filter(|r| is_error(r)).count(),
+            seconds(&all)
+        ),
+    ];
+
+    for day in days {
+        let day_rows: Vec<&ExportRow> = rows.iter().filter(|r| r.day == day).collect();
+
+        lines.push(format!(
+            r#"  <testsuite name="Day {day}" tests="{}" failures="{}" errors="{}" time="{:.9}">"#,
+            day_rows.len(),
+            day_rows.iter().filter(|r| is_failure(r)).count(),
+            day_rows.iter().filter(|r| is_error(r)).count(),
+            seconds(&day_rows)
+        ));
+
+        for row in day_rows {
+            let name = if row.part == PARSE_PART {
+                "parse".to_string()
+            } else {
+                format!("part_{}", row.part)
+            };
+
+            let time = row.duration.unwrap_or_default().as_secs_f64();
+            let testcase =
+                format!(r#"    <testcase classname="day_{day}" name="{name}" time="{time:.9}""#);
+
+            let message = match row.outcome {
+                Outcome::Passed | Outcome::Unchecked => None,
+                Outcome::Failed => Some((
+                    "failure",
+                    format!(
+                        "expected {}, got {}",
+                        row.expected.as_deref().unwrap_or_default(),
+                        row.answer.as_deref().unwrap_or_default()
+                    ),
+                )),
+                Outcome::Unsolved => Some(("failure", "no answer".to_string())),
+                Outcome::NotRun => Some(("error", format!("not run: {}", status_str(&row.status)))),
+            };
+
+            match message {
+                None => lines.push(format!("{testcase}/>")),
+                Some((tag, message)) => {
+                    lines.push(format!("{testcase}>"));
+                    lines.push(format!(
+                        r#"      <{tag} message="{}"/>"#,
+                        escape_xml(&message)
+                    ));
+                    lines.push("    </testcase>".into());
+                }
+            }
+        }
+
+        lines.push("  </testsuite>".into());
+    }
+
+    lines.push("</testsuites>".into());
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, render, ExportFormat, ExportRow, Outcome};
+    use crate::{
+        day,
+        template::{
+            answers::Answers,
+            report::mock_report,
+            run_multi::{DayRun, DayStatus, MultiRun},
+        },
+    };
+
+    fn get_mock_rows() -> Vec<ExportRow> {
+        let run = MultiRun {
+            days: vec![
+                DayRun {
+                    day: day!(1),
+                    status: DayStatus::Ran,
+                    reports: vec![
+                        mock_report(0, None, &[500], true),
+                        mock_report(1, Some("42"), &[1000], true),
+                        mock_report(2, Some("a,b"), &[2000], true),
+                    ],
+                    steps: None,
+                },
+                DayRun {
+                    day: day!(2),
+                    status: DayStatus::Panicked,
+                    reports: vec![mock_report(1, None, &[3000], true)],
+                    steps: None,
+                },
+                DayRun {
+                    day: day!(3),
+                    status: DayStatus::Missing,
+                    reports: vec![],
+                    steps: None,
+                },
+            ],
+            timings: None,
+        };
+
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(2), 1, "7");
+
+        collect(&run, &answers)
+    }
+
+    #[test]
+    fn collects_outcomes() {
+        let rows = get_mock_rows();
+        let outcomes: Vec<(u8, Outcome)> = rows.iter().map(|r| (r.part, r.outcome)).collect();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                (0, Outcome::Passed),
+                (1, Outcome::Passed),
+                (2, Outcome::Unchecked),
+                (1, Outcome::Unsolved),
+                (2, Outcome::NotRun),
+            ]
+        );
+        assert_eq!(rows[3].expected, Some("7".into()));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let expected = [
+            "day,part,status,outcome,answer,expected,nanos,samples",
+            "01,0,ran,passed,,,500,1",
+            "01,1,ran,passed,42,42,1000,1",
+            "01,2,ran,unchecked,\"a,b\",,2000,1",
+            "02,1,panicked,unsolved,,7,3000,1",
+            "02,2,panicked,not_run,,,,0",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&get_mock_rows(), ExportFormat::Csv), expected);
+    }
+
+    #[test]
+    fn exports_json_lines() {
+        let output = render(&get_mock_rows(), ExportFormat::JsonLines);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains(r#""answer":"42""#));
+        assert!(lines[4].contains(r#""nanos":null"#));
+        assert!(lines[4].contains(r#""outcome":"not_run""#));
+    }
+
+    #[test]
+    fn exports_junit() {
+        let expected = [
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<testsuites name="advent_of_code" tests="5" failures="1" errors="1" time="0.000006500">"#,
+            r#"  <testsuite name="Day 01" tests="3" failures="0" errors="0" time="0.000003500">"#,
+            r#"    <testcase classname="day_01" name="parse" time="0.000000500"/>"#,
+            r#"    <testcase classname="day_01" name="part_1" time="0.000001000"/>"#,
+            r#"    <testcase classname="day_01" name="part_2" time="0.000002000"/>"#,
+            r#"  </testsuite>"#,
+            r#"  <testsuite name="Day 02" tests="2" failures="1" errors="1" time="0.000003000">"#,
+            r#"    <testcase classname="day_02" name="part_1" time="0.000003000">"#,
+            r#"      <failure message="no answer"/>"#,
+            r#"    </testcase>"#,
+            r#"    <testcase classname="day_02" name="part_2" time="0.000000000">"#,
+            r#"      <error message="not run: panicked"/>"#,
+            r#"    </testcase>"#,
+            r#"  </testsuite>"#,
+            r#"</testsuites>"#,
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(render(&get_mock_rows(), ExportFormat::Junit), expected);
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            "jsonl".parse::<ExportFormat>().unwrap(),
+            ExportFormat::JsonLines
+        );
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,274 @@
+/// The examples and answers found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Extracted {
+    /// The first code block of part one.
+    pub example: Option<String>,
+    /// The first code block of part two, if it differs from the example of part one.
+    pub example_2: Option<String>,
+    /// The last emphasised code of part one.
+    pub answer_1: Option<String>,
+    /// The last emphasised code of part two.
+    pub answer_2: Option<String>,
+}
+
+impl Extracted {
+    /// The answer of a part.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.answer_1.as_deref(),
+            2 => self.answer_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// A part of the description: its code blocks and the text outside of them.
+#[derive(Default)]
+struct Section {
+    code_blocks: Vec<String>,
+    text: String,
+}
+
+/// Propose examples and their expected answers from the markdown of a puzzle description.
+///
+/// Works with the markdown written by aoc-cli and by the native client: examples are fenced code blocks,
+/// answers are emphasised code, e.g. `*11*`. The heuristics are simple, so the results should be reviewed.
+pub fn extract(markdown: &str) -> Extracted {
+    let [part_1, part_2] = split_parts(markdown);
+
+    let example = part_1.code_blocks.first().cloned();
+    let example_2 = part_2
+        .code_blocks
+        .first()
+        .filter(|block| Some(*block) != example.as_ref())
+        .cloned();
+
+    Extracted {
+        example,
+        example_2,
+        answer_1: emphasised_code(&part_1.text).pop(),
+        answer_2: emphasised_code(&part_2.text).pop(),
+    }
+}
+
+/// Split the description into part one and part two, at the heading of part two.
+fn split_parts(markdown: &str) -> [Section; 2] {
+    let mut sections: [Section; 2] = Default::default();
+    let mut part = 0;
+    let mut code_block: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match code_block.take() {
+                Some(lines) => sections[part].code_blocks.push(lines.join("\n") + "\n"),
+                None => code_block = Some(vec![]),
+            }
+            continue;
+        }
+
+        if let Some(lines) = code_block.as_mut() {
+            lines.push(line);
+            continue;
+        }
+
+        if is_heading(line) && line.contains("Part Two") {
+            part = 1;
+        }
+
+        sections[part].text.push_str(line);
+        sections[part].text.push('\n');
+    }
+
+    sections
+}
+
+/// Headings are written as `## --- Part Two ---` or, by aoc-cli, `## \--- Part Two --- ##`.
+fn is_heading(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with('#') || line.starts_with("\\---") || line.starts_with("---")
+}
+
+/// All emphasised code in the text, i.e. `` `*11*` `` or `` *`11`* ``, in order.
+fn emphasised_code(text: &str) -> Vec<String> {
+    let mut results = vec![];
+    let mut rest = text;
+
+    loop {
+        let next = [("`*", "*`"), ("*`", "`*")]
+            .into_iter()
+            .filter_map(|(open, close)| rest.find(open).map(|start| (start, open, close)))
+            .min();
+
+        let Some((start, open, close)) = next else {
+            break;
+        };
+
+        let after = &rest[start + open.len()..];
+        match after.find(close) {
+            Some(end) if !after[..end].is_empty() && !after[..end].contains(['\n', '`', '*']) => {
+                results.push(after[..end].to_string());
+                rest = &after[end + close.len()..];
+            }
+            // NOTE: the markers are ASCII, so skipping a byte stays on a char boundary.
+            _ => rest = &rest[start + 1..],
+        }
+    }
+
+    results
+}
+
+/// Fill the assertions of the generated tests of a solution with the extracted answers.
+///
+/// Only assertions that still compare with `None` are replaced, and only with numeric answers,
+/// as the template parts return numbers. If part two has its own example, its test reads `<day>-2.txt`.
+pub fn fill_assertions(source: &str, extracted: &Extracted) -> String {
+    let mut source = source.to_string();
+
+    for (part, test) in [(1, "fn test_part_one()"), (2, "fn test_part_two()")] {
+        let Some(start) = source.find(test) else {
+            continue;
+        };
+        let end = source[start + test.len()..]
+            .find("fn ")
+            .map_or(source.len(), |i| start + test.len() + i);
+
+        let mut body = source[start..end].to_string();
+
+        if let Some(answer) = extracted.answer(part).filter(|a| is_number(a)) {
+            body = body.replacen(
+                "assert_eq!(result, None);",
+                &format!("assert_eq!(result, Some({answer}));"),
+                1,
+            );
+        }
+
+        if part == 2 && extracted.example_2.is_some() {
+            body = body.replacen(
+                "read_file(\"examples\", PUZZLE)",
+                "read_file_part(\"examples\", PUZZLE, 2)",
+                1,
+            );
+        }
+
+        source.replace_range(start..end, &body);
+    }
+
+    source
+}
+
+fn is_number(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, fill_assertions, Extracted};
+
+    const MARKDOWN: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+For example:
+
+```
+3   4
+4   3
+```
+
+The smallest number in the left list is `*1*`.
+
+In this example, the total distance is `*11*`!
+
+\--- Part Two --- ##
+----------
+
+This time, you'll need to figure out how often each number appears:
+
+```
+3   4
+4   3
+```
+
+So, in this example, the similarity score is *`31`*.
+
+Your puzzle answer was `1234`.
+";
+
+    const TEMPLATE: &str = r#"pub fn part_one(input: &str) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        assert_eq!(
+            extract(MARKDOWN),
+            Extracted {
+                example: Some("3   4\n4   3\n".into()),
+                example_2: None,
+                answer_1: Some("11".into()),
+                answer_2: Some("31".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn extracts_examples_of_part_two() {
+        let markdown = "## --- Day 3 ---\n```\nmul(2,4)\n```\nadds up to `*8*`.\n## --- Part Two ---\n```\ndon't()mul(2,4)\n```\nresult is `*0*`.\n";
+
+        let extracted = extract(markdown);
+        assert_eq!(extracted.example.as_deref(), Some("mul(2,4)\n"));
+        assert_eq!(extracted.example_2.as_deref(), Some("don't()mul(2,4)\n"));
+        assert_eq!(extracted.answer_2.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn handles_descriptions_without_part_two() {
+        let extracted = extract("## --- Day 3 ---\nNo example, but `*a*` and `*b*`.\n");
+        assert_eq!(extracted.example, None);
+        assert_eq!(extracted.answer_1.as_deref(), Some("b"));
+        assert_eq!(extracted.answer_2, None);
+    }
+
+    #[test]
+    fn fills_assertions() {
+        let extracted = Extracted {
+            example: Some("1".into()),
+            example_2: Some("2".into()),
+            answer_1: Some("11".into()),
+            answer_2: Some("abc".into()),
+        };
+
+        let filled = fill_assertions(TEMPLATE, &extracted);
+        assert!(filled.contains("assert_eq!(result, Some(11));"));
+        // non-numeric answers are left for the user.
+        assert_eq!(filled.matches("assert_eq!(result, None);").count(), 1);
+        assert!(filled.contains(
+            r#"part_two(&advent_of_code::template::read_file_part("examples", PUZZLE, 2))"#
+        ));
+        assert!(filled
+            .contains(r#"part_one(&advent_of_code::template::read_file("examples", PUZZLE))"#));
+
+        // filled assertions are not replaced again.
+        assert_eq!(fill_assertions(&filled, &extracted), filled);
+    }
+}
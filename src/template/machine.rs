@@ -0,0 +1,214 @@
+//! Describes the machine that benchmarks were run on, so that timings of different machines can be kept apart.
+
+use std::{collections::HashMap, env, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+pub const ENV_PROFILE: &str = "AOC_PROFILE";
+pub const ENV_REFERENCE_PROFILE: &str = "AOC_REFERENCE_PROFILE";
+
+/// Name of the profile used if none is selected, and of timings stored before profiles were tracked.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// A named machine profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MachineProfile {
+    pub name: String,
+    /// CPU model as reported by `/proc/cpuinfo`.
+    pub cpu: Option<String>,
+    pub cores: Option<usize>,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+}
+
+impl MachineProfile {
+    /// Creates a profile with the given name, describing the current machine.
+    pub fn detect(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            cpu: fs::read_to_string("/proc/cpuinfo")
+                .ok()
+                .and_then(|cpuinfo| parse_cpu_model(&cpuinfo)),
+            cores: thread::available_parallelism().ok().map(usize::from),
+            rustc: rustc_version(),
+        }
+    }
+
+    /// The selected profile name: the `--profile` option, then `AOC_PROFILE`, then the default profile.
+    pub fn selected_name(name: Option<String>) -> String {
+        name.or_else(|| env::var(ENV_PROFILE).ok())
+            .unwrap_or_else(|| DEFAULT_PROFILE.into())
+    }
+
+    /// The profile rendered to the readme as reference, set by `AOC_REFERENCE_PROFILE`.
+    pub fn reference_name() -> Option<String> {
+        env::var(ENV_REFERENCE_PROFILE).ok()
+    }
+}
+
+impl Display for MachineProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let details: Vec<String> = [
+            self.cpu.clone(),
+            self.cores.map(|cores| format!("{cores} cores")),
+            self.rustc.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+
+        if details.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{} ({})", self.name, details.join(", "))
+        }
+    }
+}
+
+fn parse_cpu_model(cpuinfo: &str) -> Option<String> {
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+fn rustc_version() -> Option<String> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let version = String::from_utf8(output.stdout).ok()?;
+    Some(version.trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MachineProfile> for JsonValue {
+    fn from(value: &MachineProfile) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+
+        map.insert(
+            "cpu".into(),
+            value.cpu.clone().map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "cores".into(),
+            value
+                .cores
+                .map_or(JsonValue::Null, |cores| JsonValue::Number(cores as f64)),
+        );
+
+        map.insert(
+            "rustc".into(),
+            value
+                .rustc
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MachineProfile {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected profile to be a JSON object.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected profile.name to be a string.")?
+            .clone();
+
+        let optional_string = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("Expected profile.{key} to be null or string.")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = match json.get("cores") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|cores| *cores as usize)
+                    .ok_or("Expected profile.cores to be null or a number.")?,
+            ),
+        };
+
+        Ok(MachineProfile {
+            name,
+            cpu: optional_string("cpu")?,
+            cores,
+            rustc: optional_string("rustc")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cpu_model, MachineProfile};
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_cpu_model() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: GenuineIntel\nmodel name\t: Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz\n";
+        assert_eq!(
+            parse_cpu_model(cpuinfo),
+            Some("Intel(R) Core(TM) i7-8550U CPU @ 1.80GHz".into())
+        );
+        assert_eq!(parse_cpu_model("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let profile = MachineProfile {
+            name: "ci".into(),
+            cpu: Some("AMD EPYC 7763".into()),
+            cores: Some(4),
+            rustc: None,
+        };
+
+        let json = JsonValue::from(&profile);
+        assert_eq!(MachineProfile::try_from(&json).unwrap(), profile);
+    }
+
+    #[test]
+    fn displays_details() {
+        let profile = MachineProfile {
+            name: "laptop".into(),
+            cpu: Some("Apple M1".into()),
+            cores: Some(8),
+            rustc: Some("rustc 1.83.0".into()),
+        };
+        assert_eq!(
+            profile.to_string(),
+            "laptop (Apple M1, 8 cores, rustc 1.83.0)"
+        );
+
+        let profile = MachineProfile {
+            name: "default".into(),
+            cpu: None,
+            cores: None,
+            rustc: None,
+        };
+        assert_eq!(profile.to_string(), "default");
+    }
+}
@@ -1,68 +1,229 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
+pub mod allocator;
 pub mod aoc_cli;
+#[cfg(feature = "client")]
+pub mod aoc_client;
+pub mod bench;
 pub mod commands;
+pub mod examples;
+pub mod export;
 pub mod runner;
+pub mod templates;
 
+pub use answer::Answer;
 pub use day::*;
+pub use params::Params;
+pub use puzzle::*;
+pub use run_multi::Timeouts;
 
+mod answer;
+mod answers;
+mod benchmark_chart;
 mod day;
+mod extract;
+mod machine;
+mod params;
+mod puzzle;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Current time in seconds since the unix epoch.
+pub(crate) fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 /// Helper function that reads a text file to a string.
+///
+/// Accepts a [`Day`] of the configured year or a [`Puzzle`] of any year.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, &format!("{}.txt", puzzle.day())));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, &format!("{}-{part}.txt", puzzle.day())));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, last parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse` (e.g. `solution!(1, parse)` or `solution!(1, parse, 2)`) makes the runner call a `parse`
+/// function once, time it separately, and pass a reference to its output to both parts.
+///
+/// Passing `params = <type>` (e.g. `solution!(18, params = Memory)` or `solution!(18, parse, params = Memory, 1)`)
+/// passes a reference to the [`Params::real`](crate::template::Params::real) values of the type to both parts as a second argument.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [], [part_two, 2]);
+    };
+    ($day:expr, parse) => {
+        $crate::solution!(@impl $day, [parse], [], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, 1) => {
+        $crate::solution!(@impl $day, [parse], [], [part_one, 1]);
+    };
+    ($day:expr, parse, 2) => {
+        $crate::solution!(@impl $day, [parse], [], [part_two, 2]);
+    };
+    ($day:expr, params = $params:ty) => {
+        $crate::solution!(@impl $day, [], [$params], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, params = $params:ty, 1) => {
+        $crate::solution!(@impl $day, [], [$params], [part_one, 1]);
+    };
+    ($day:expr, params = $params:ty, 2) => {
+        $crate::solution!(@impl $day, [], [$params], [part_two, 2]);
+    };
+    ($day:expr, parse, params = $params:ty) => {
+        $crate::solution!(@impl $day, [parse], [$params], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse, params = $params:ty, 1) => {
+        $crate::solution!(@impl $day, [parse], [$params], [part_one, 1]);
+    };
+    ($day:expr, parse, params = $params:ty, 2) => {
+        $crate::solution!(@impl $day, [parse], [$params], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($parse:ident)?], $params:tt, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle. Its year is read from the binary name, e.g. `2023-01`.
+        const PUZZLE: $crate::template::Puzzle =
+            $crate::template::Puzzle::for_bin(env!("CARGO_BIN_NAME"), DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::allocator::CountingAllocator =
+            $crate::template::allocator::CountingAllocator;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            $crate::solution!(@params $params, params, real());
+            report_steps($crate::solution!(@has_parse $($parse)?), &[$($part),*]);
+            $( let input = run_parse($parse, &input); )?
+            $( run_part(|input| $crate::solution!(@call $func, input, params, $params), &input, PUZZLE, $part); )*
+        }
+    };
+
+    (@has_parse) => { false };
+    (@has_parse $parse:ident) => { true };
+
+    // NOTE: the variable names are passed in, so that they resolve to the variables of the caller.
+    (@params [], $params_var:ident, $($constructor:tt)*) => {};
+    (@params [$params:ty], $params_var:ident, $($constructor:tt)*) => {
+        let $params_var = <$params as $crate::template::Params>::$($constructor)*;
+    };
+    (@call $func:expr, $input:ident, $params_var:ident, []) => {
+        $func($input)
+    };
+    (@call $func:expr, $input:ident, $params_var:ident, [$params:ty]) => {
+        $func($input, &$params_var)
+    };
+}
+
+/// Generates a test per named example in `data/examples/<day>.json` that checks the expected answers of each part.
+///
+/// Takes the same optional `parse`, `params` and part arguments as [`solution!`], followed by a `;` and the names of
+/// the examples, e.g. `examples!(parse; small, large)`. Use it inside the `tests` module of a solution.
+/// Parameters are created with [`Params::for_example`](crate::template::Params::for_example).
+/// An additional test `examples_have_tests` fails if the file contains an example that is not listed.
+#[macro_export]
+macro_rules! examples {
+    (parse; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [parse], [], [[part_one, 1] [part_two, 2]]; $($name),+);
+    };
+    (parse, 1; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [parse], [], [[part_one, 1]]; $($name),+);
+    };
+    (parse, 2; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [parse], [], [[part_two, 2]]; $($name),+);
+    };
+    (parse, params = $params:ty; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [parse], [$params], [[part_one, 1] [part_two, 2]]; $($name),+);
+    };
+    (parse, params = $params:ty, 1; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [parse], [$params], [[part_one, 1]]; $($name),+);
+    };
+    (parse, params = $params:ty, 2; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [parse], [$params], [[part_two, 2]]; $($name),+);
+    };
+    (params = $params:ty; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [], [$params], [[part_one, 1] [part_two, 2]]; $($name),+);
+    };
+    (params = $params:ty, 1; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [], [$params], [[part_one, 1]]; $($name),+);
+    };
+    (params = $params:ty, 2; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [], [$params], [[part_two, 2]]; $($name),+);
+    };
+    (1; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [], [], [[part_one, 1]]; $($name),+);
+    };
+    (2; $($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [], [], [[part_two, 2]]; $($name),+);
+    };
+    ($($name:ident),+ $(,)?) => {
+        $crate::examples!(@impl [], [], [[part_one, 1] [part_two, 2]]; $($name),+);
+    };
+
+    (@impl $parse:tt, $params:tt, $parts:tt; $($name:ident),+) => {
+        $( $crate::examples!(@test $name, $parse, $params, $parts); )+
+
+        #[test]
+        fn examples_have_tests() {
+            $crate::template::examples::assert_covered(PUZZLE, &[$(stringify!($name)),+]);
+        }
+    };
+
+    (@test $name:ident, [$($parse:ident)?], $params:tt, [$( [$func:ident, $part:expr] )*]) => {
+        #[test]
+        fn $name() {
+            let example = $crate::template::examples::example(PUZZLE, stringify!($name));
+            $crate::solution!(@params $params, params, for_example(&example));
+            let input = example.read_input(PUZZLE);
+            let input = input.as_str();
+            $( let input = &$parse(input); )?
+            $( example.check($part, || $crate::solution!(@call $func, input, params, $params)); )*
         }
     };
 }
@@ -0,0 +1,20 @@
+//! Parameters of a solution that differ between the examples and the real input.
+//!
+//! Passed to both parts when a solution is declared with `params = <type>`, e.g. `solution!(18, params = Memory)`.
+
+use crate::template::examples::Example;
+
+pub trait Params: Sized {
+    /// Values for the real input, used by `cargo solve`.
+    fn real() -> Self;
+
+    /// Values for the examples.
+    fn example() -> Self;
+
+    /// Values for a named example of `data/examples/<day>.json`, used by the [`examples!`](crate::examples) macro.
+    /// Override this to read the `params` of the example, defaults to [`Params::example`].
+    fn for_example(example: &Example) -> Self {
+        let _ = example;
+        Self::example()
+    }
+}
@@ -0,0 +1,221 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The year of the first event.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent (i.e. 2015 or later).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `None` for the year configured by `AOC_YEAR`, whose files use the flat layout.
+    pub fn explicit(year: Option<Self>) -> Option<Self> {
+        year.filter(|year| Some(*year) != Self::from_env())
+    }
+
+    /// Returns the year configured by the `AOC_YEAR` environment variable, if any.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A day of a specific event.
+///
+/// Puzzles of the year configured by `AOC_YEAR` have no explicit year and use the flat layout
+/// (`src/bin/01.rs`, `data/inputs/01.txt`). Puzzles of other years are namespaced by their year
+/// (`src/bin/2023-01.rs`, `data/2023/inputs/01.txt`).
+///
+/// # Display
+/// This value displays as the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, template::{Puzzle, Year}};
+/// let puzzle = Puzzle::new(Year::new(2015), day!(8));
+/// assert_eq!(puzzle.to_string(), "2015-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    year: Option<Year>,
+    day: Day,
+}
+
+impl Puzzle {
+    /// Creates a [`Puzzle`]. A year equal to the configured `AOC_YEAR` is treated as no explicit year.
+    pub fn new(year: Option<Year>, day: Day) -> Self {
+        Self {
+            year: Year::explicit(year),
+            day,
+        }
+    }
+
+    /// Creates the [`Puzzle`] solved by a binary, reading the year from a `YYYY-NN` binary name.
+    pub const fn for_bin(bin_name: &str, day: Day) -> Self {
+        let bytes = bin_name.as_bytes();
+
+        if bytes.len() < 6 || bytes[4] != b'-' {
+            return Self { year: None, day };
+        }
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < 4 {
+            if !bytes[i].is_ascii_digit() {
+                return Self { year: None, day };
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        Self {
+            year: Some(Year(year)),
+            day,
+        }
+    }
+
+    pub fn day(self) -> Day {
+        self.day
+    }
+
+    /// The explicit year of the puzzle, `None` for the configured year.
+    pub fn explicit_year(self) -> Option<Year> {
+        self.year
+    }
+
+    /// The year of the puzzle, falling back to the configured `AOC_YEAR`.
+    pub fn year(self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    /// Path of the solution binary, e.g. `./src/bin/2023-01.rs`.
+    pub fn bin_path(self) -> String {
+        format!("./src/bin/{self}.rs")
+    }
+
+    /// Path of a file belonging to this puzzle in `data/`, e.g. `data/2023/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, file_name: &str) -> String {
+        data_path(self.year, &format!("{folder}/{file_name}"))
+    }
+}
+
+impl From<Day> for Puzzle {
+    fn from(day: Day) -> Self {
+        Self { year: None, day }
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}-{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/// Path of a file in `data/`, namespaced by an explicit year.
+pub fn data_path(year: Option<Year>, path: &str) -> String {
+    match year {
+        Some(year) => format!("data/{year}/{path}"),
+        None => format!("data/{path}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{data_path, Puzzle, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), Year(2023));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("23".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_years_from_bin_names() {
+        let puzzle = Puzzle::for_bin("2023-01", day!(1));
+        assert_eq!(puzzle.explicit_year(), Some(Year(2023)));
+        assert_eq!(puzzle.to_string(), "2023-01");
+
+        let puzzle = Puzzle::for_bin("01", day!(1));
+        assert_eq!(puzzle.explicit_year(), None);
+        assert_eq!(puzzle.to_string(), "01");
+
+        assert_eq!(Puzzle::for_bin("ab-cd-01", day!(1)).explicit_year(), None);
+    }
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = Puzzle::for_bin("2023-01", day!(1));
+        assert_eq!(puzzle.bin_path(), "./src/bin/2023-01.rs");
+        assert_eq!(
+            puzzle.data_path("inputs", "01.txt"),
+            "data/2023/inputs/01.txt"
+        );
+
+        let puzzle = Puzzle::from(day!(1));
+        assert_eq!(puzzle.bin_path(), "./src/bin/01.rs");
+        assert_eq!(puzzle.data_path("inputs", "01.txt"), "data/inputs/01.txt");
+
+        assert_eq!(data_path(None, "answers.json"), "data/answers.json");
+    }
+}
@@ -1,12 +1,17 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fs, io, str::FromStr, time::Duration};
 
+use crate::template::machine::MachineProfile;
+use crate::template::stats::{format_bytes, MemoryStats};
 use crate::template::timings::Timings;
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+pub const ENV_SORT: &str = "AOC_BENCHMARK_SORT";
+pub const ENV_TEMPLATE: &str = "AOC_BENCHMARK_TEMPLATE";
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -52,78 +57,265 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+/// The default layout of a benchmark table, see [`TableTemplate`].
+const DEFAULT_TEMPLATE: &str = "| Day | Parse | Part 1 | Part 2 | Total | Share | Solved |
+| :---: | :---: | :---: | :---: | :---: | :---: | :---: |
+| [Day {day}]({path}) | `{parse}` | `{part_1}` | `{part_2}` | `{total}` | {share} | {solved} |
+
+**Total: {overall}**";
+
+/// The order of the days in a benchmark table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TableSort {
+    #[default]
+    Day,
+    /// Slowest days first.
+    Cost,
+}
+
+impl FromStr for TableSort {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "cost" => Ok(Self::Cost),
+            _ => Err(Error::Parser(format!(
+                "unknown sort order \"{s}\", expected \"day\" or \"cost\"."
+            ))),
+        }
     }
+}
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+/// The layout of a benchmark table.
+/// The line containing `{day}` is rendered for every day, the lines before and after it once.
+///
+/// Rows can use the placeholders `{day}`, `{path}`, `{parse}`, `{part_1}`, `{part_2}`, `{total}`, `{share}` and `{solved}`,
+/// as well as the peak heap usage `{parse_memory}`, `{part_1_memory}` and `{part_2_memory}`. All lines can use `{overall}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableTemplate {
+    header: Vec<String>,
+    row: String,
+    footer: Vec<String>,
+}
+
+impl Default for TableTemplate {
+    fn default() -> Self {
+        DEFAULT_TEMPLATE.parse().unwrap()
+    }
+}
+
+impl FromStr for TableTemplate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines: Vec<&str> = s.trim_end().lines().collect();
+
+        let row = lines
+            .iter()
+            .position(|line| line.contains("{day}"))
+            .ok_or_else(|| Error::Parser("table template has no row with `{day}`.".into()))?;
+
+        Ok(Self {
+            header: lines[..row].iter().map(|l| (*l).to_string()).collect(),
+            row: lines[row].to_string(),
+            footer: lines[row + 1..].iter().map(|l| (*l).to_string()).collect(),
+        })
+    }
+}
 
+/// Configuration of the benchmark tables, read from the environment.
+#[derive(Clone, Debug, Default)]
+pub struct TableOptions {
+    /// Only render the table of this profile.
+    pub reference: Option<String>,
+    pub sort: TableSort,
+    pub template: TableTemplate,
+}
+
+impl TableOptions {
+    /// Reads `AOC_REFERENCE_PROFILE`, `AOC_BENCHMARK_SORT` and the template file at `AOC_BENCHMARK_TEMPLATE`.
+    pub fn from_env() -> Result<Self, Error> {
+        let sort = match env::var(ENV_SORT) {
+            Ok(sort) => sort.parse()?,
+            Err(_) => TableSort::default(),
+        };
+
+        let template = match env::var(ENV_TEMPLATE) {
+            Ok(path) => fs::read_to_string(path)?.parse()?,
+            Err(_) => TableTemplate::default(),
+        };
+
+        Ok(Self {
+            reference: MachineProfile::reference_name(),
+            sort,
+            template,
+        })
+    }
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(|| "-".into(), |m| format_bytes(m.peak_bytes))
+}
+
+/// Renders the table of a single profile.
+fn construct_table(
+    timings: Timings,
+    profile: Option<&MachineProfile>,
+    options: &TableOptions,
+) -> Vec<String> {
+    let overall_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+    let overall = format!("{:.2}ms", overall_nanos / 1_000_000_f64);
+    let template = &options.template;
+
+    let mut lines: Vec<String> = vec![];
+
+    if let Some(profile) = profile {
+        lines.push(format!("_Measured on {profile}._"));
+        lines.push(String::new());
+    }
+
+    lines.extend(
+        template
+            .header
+            .iter()
+            .map(|l| l.replace("{overall}", &overall)),
+    );
+
+    let mut data = timings.data;
+    if options.sort == TableSort::Cost {
+        data.sort_by(|a, b| {
+            b.total_nanos
+                .total_cmp(&a.total_nanos)
+                .then(a.day.cmp(&b.day))
+        });
+    }
+
+    for timing in data {
+        let share = if overall_nanos > 0.0 {
+            format!("{:.1}%", timing.total_nanos / overall_nanos * 100.0)
+        } else {
+            "-".into()
+        };
+
+        let solved = if timing.part_1.is_some() && timing.part_2.is_some() {
+            "✅"
+        } else {
+            "❌"
+        };
+
+        lines.push(
+            template
+                .row
+                .replace("{day}", &timing.day.into_inner().to_string())
+                .replace("{path}", &get_path_for_bin(timing.day))
+                .replace("{parse}", timing.parse.as_deref().unwrap_or("-"))
+                .replace("{part_1}", timing.part_1.as_deref().unwrap_or("-"))
+                .replace("{part_2}", timing.part_2.as_deref().unwrap_or("-"))
+                .replace("{total}", &format_nanos(timing.total_nanos))
+                .replace("{share}", &share)
+                .replace("{solved}", solved)
+                .replace("{parse_memory}", &format_memory(timing.parse_memory))
+                .replace("{part_1_memory}", &format_memory(timing.part_1_memory))
+                .replace("{part_2_memory}", &format_memory(timing.part_2_memory))
+                .replace("{overall}", &overall),
+        );
+    }
+
+    lines.extend(
+        template
+            .footer
+            .iter()
+            .map(|l| l.replace("{overall}", &overall)),
+    );
+
+    lines
+}
+
+/// Renders the table of the reference profile if given, one table per profile otherwise.
+fn construct_tables(prefix: &str, timings: &Timings, options: &TableOptions) -> String {
+    let names = match &options.reference {
+        Some(reference) => vec![reference.clone()],
+        None => timings.profile_names(),
+    };
+
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} Benchmarks")];
+
+    for name in &names {
+        lines.push(String::new());
+
+        // a single table does not need a heading of its own.
+        if names.len() > 1 {
+            lines.push(format!("{prefix}# {name}"));
+            lines.push(String::new());
+        }
+
+        let profile = timings.profiles.iter().find(|p| &p.name == name);
+        lines.extend(construct_table(timings.for_profile(name), profile, options));
+    }
+
+    lines.push(MARKER.into());
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(s: &mut String, timings: &Timings, options: &TableOptions) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_tables("##", timings, options);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Updates the benchmarks in the readme, see [`construct_tables`] and [`TableOptions`].
+pub fn update(timings: &Timings) -> Result<(), Error> {
     let path = "README.md";
+    let options = TableOptions::from_env()?;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, &options)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, TableOptions, TableSort, TableTemplate, MARKER};
+    use crate::{
+        day,
+        template::machine::MachineProfile,
+        template::stats::MemoryStats,
+        template::timings::{Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    parse: Some("5ms".into()),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    total_nanos: 3e+7,
+                    ..Timing::new(day!(1), "default")
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    total_nanos: 7e+7,
+                    ..Timing::new(day!(2), "default")
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    total_nanos: 9e+7,
+                    ..Timing::new(day!(4), "default")
                 },
             ],
+            history: vec![],
+            profiles: vec![],
         }
     }
 
@@ -131,28 +323,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), &TableOptions::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), &TableOptions::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), &TableOptions::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), &TableOptions::default()).unwrap();
+        update_content(&mut s, &get_mock_timings(), &TableOptions::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +352,18 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings(), &TableOptions::default()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 | Total | Share | Solved |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` | `30.0ms` | 15.8% | ✅ |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` | `70.0ms` | 36.8% | ✅ |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` | `90.0ms` | 47.4% | ✅ |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +372,146 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    fn get_mock_profile_timings() -> Timings {
+        let mut timings = get_mock_timings();
+        timings.data[2].profile = "ci".into();
+        timings.profiles = vec![MachineProfile {
+            name: "ci".into(),
+            cpu: Some("AMD EPYC 7763".into()),
+            cores: Some(4),
+            rustc: None,
+        }];
+        timings
+    }
+
+    #[test]
+    fn format_benchmarks_per_profile() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &get_mock_profile_timings(),
+            &TableOptions::default(),
+        )
+        .unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "### ci",
+            "",
+            "_Measured on ci (AMD EPYC 7763, 4 cores)._",
+            "",
+            "| Day | Parse | Part 1 | Part 2 | Total | Share | Solved |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` | `90.0ms` | 100.0% | ✅ |",
+            "",
+            "**Total: 90.00ms**",
+            "",
+            "### default",
+            "",
+            "| Day | Parse | Part 1 | Part 2 | Total | Share | Solved |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` | `30.0ms` | 30.0% | ✅ |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` | `70.0ms` | 70.0% | ✅ |",
+            "",
+            "**Total: 100.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_reference_benchmarks() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &get_mock_profile_timings(),
+            &TableOptions {
+                reference: Some("ci".into()),
+                ..TableOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(s.contains("### "), false);
+        assert_eq!(s.contains("_Measured on ci"), true);
+        assert_eq!(s.contains("Day 1"), false);
+        assert_eq!(s.contains("**Total: 90.00ms**"), true);
+    }
+
+    #[test]
+    fn sorts_benchmarks_by_cost() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let options = TableOptions {
+            sort: TableSort::Cost,
+            ..TableOptions::default()
+        };
+        update_content(&mut s, &timings, &options).unwrap();
+
+        let rows: Vec<&str> = s.lines().filter(|l| l.starts_with("| [Day")).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].starts_with("| [Day 4]"));
+        assert!(rows[1].starts_with("| [Day 2]"));
+        assert!(rows[1].ends_with("| ❌ |"));
+        assert!(rows[2].starts_with("| [Day 1]"));
+    }
+
+    #[test]
+    fn renders_custom_templates() {
+        let template =
+            "Day | Time\n--- | ---\n{day} | {total} ({share})\n\n_{overall} in total._\n";
+        let options = TableOptions {
+            template: template.parse().unwrap(),
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &get_mock_timings(), &options).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "Day | Time",
+            "--- | ---",
+            "1 | 30.0ms (15.8%)",
+            "2 | 70.0ms (36.8%)",
+            "4 | 90.0ms (47.4%)",
+            "",
+            "_190.00ms in total._",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn renders_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            blocks: 4,
+            bytes: 4096,
+            peak_bytes: 2048,
+        });
+
+        let options = TableOptions {
+            template: "{day}: {part_1_memory} / {part_2_memory}".parse().unwrap(),
+            ..TableOptions::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, &timings, &options).unwrap();
+        assert!(s.contains("\n1: 2.0 KiB / -\n"));
+        assert!(s.contains("\n2: - / -\n"));
+    }
+
+    #[test]
+    fn errors_for_templates_without_rows() {
+        assert!("| Day |".parse::<TableTemplate>().is_err());
+        assert!("cost".parse::<TableSort>().is_ok());
+        assert!("name".parse::<TableSort>().is_err());
+    }
 }
@@ -0,0 +1,289 @@
+//! Machine-readable results that solution binaries emit for `run_multi`.
+//!
+//! When the environment variable [`ENV_REPORT_FILE`] is set, the runner appends one JSON line per part to that file.
+//! This keeps the human-readable output free-form while the parent process gets structured data.
+
+use std::{
+    collections::HashMap,
+    fs::OpenOptions,
+    io::{self, Write},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::stats::{MemoryStats, Stats};
+
+pub const ENV_REPORT_FILE: &str = "AOC_REPORT_FILE";
+
+/// Value of [`PartReport::part`] for the shared parse step of a solution.
+pub const PARSE_PART: u8 = 0;
+
+/// The result of running a single part (or the parse step) of a solution.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartReport {
+    /// `1` or `2` for the parts, [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    /// The answer as printed, `None` if the part returned `None`.
+    pub answer: Option<String>,
+    pub stats: Stats,
+    /// Heap usage of the first run, if the solution was built with an allocator that measures it.
+    pub memory: Option<MemoryStats>,
+    /// `true` if the part was benched with `--time`, so that its stats are a benchmark.
+    pub timed: bool,
+}
+
+/// The steps that a solution declares, reported once before the first of them runs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StepsReport {
+    /// `true` if the solution has a shared parse step.
+    pub parse: bool,
+    /// The parts the solution runs, in order.
+    pub parts: Vec<u8>,
+}
+
+/// Append a line to the file referenced by [`ENV_REPORT_FILE`], if set.
+fn append_to_env_file(line: &str) -> io::Result<()> {
+    let Ok(path) = std::env::var(ENV_REPORT_FILE) else {
+        return Ok(());
+    };
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+impl PartReport {
+    /// Append the report to the file referenced by [`ENV_REPORT_FILE`], if set.
+    pub fn write_to_env_file(&self) -> io::Result<()> {
+        append_to_env_file(&self.to_json_line())
+    }
+
+    pub fn to_json_line(&self) -> String {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(self.part)));
+        map.insert(
+            "answer".into(),
+            self.answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(self.stats.mean.as_nanos() as f64),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(self.stats.samples as f64),
+        );
+        map.insert("stats".into(), JsonValue::from(self.stats));
+        map.insert(
+            "memory".into(),
+            self.memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert("timed".into(), JsonValue::Boolean(self.timed));
+
+        // NOTE: serializing a JSON object built from valid values can not fail.
+        JsonValue::Object(map).stringify().unwrap()
+    }
+}
+
+impl FromStr for PartReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("report is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .filter(|v| *v <= 2)
+            .ok_or("Expected report.part to be 0, 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let stats = json
+            .get("stats")
+            .ok_or("Expected report to have key `stats`.")
+            .map(Stats::try_from)??;
+
+        let memory = match json.get("memory") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(MemoryStats::try_from(v)?),
+        };
+
+        let timed = json
+            .get("timed")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected report.timed to be a boolean.")?;
+
+        Ok(Self {
+            part,
+            answer: answer.cloned(),
+            stats,
+            memory,
+            timed,
+        })
+    }
+}
+
+impl StepsReport {
+    /// Append the report to the file referenced by [`ENV_REPORT_FILE`], if set.
+    pub fn write_to_env_file(&self) -> io::Result<()> {
+        append_to_env_file(&self.to_json_line())
+    }
+
+    pub fn to_json_line(&self) -> String {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("parse".into(), JsonValue::Boolean(self.parse));
+        map.insert(
+            "parts".into(),
+            JsonValue::Array(
+                self.parts
+                    .iter()
+                    .map(|part| JsonValue::Number(f64::from(*part)))
+                    .collect(),
+            ),
+        );
+
+        // NOTE: serializing a JSON object built from valid values can not fail.
+        JsonValue::Object(map).stringify().unwrap()
+    }
+}
+
+impl FromStr for StepsReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("report is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let parse = json
+            .get("parse")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected report.parse to be a boolean.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let parts = json
+            .get("parts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|parts| {
+                parts
+                    .iter()
+                    .map(|v| {
+                        v.get::<f64>()
+                            .map(|v| *v as u8)
+                            .filter(|v| (1..=2).contains(v))
+                    })
+                    .collect::<Option<Vec<u8>>>()
+            })
+            .ok_or("Expected report.parts to be an array of 1 or 2.")?;
+
+        Ok(Self { parse, parts })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A report with samples in nanoseconds, shared by the tests of the modules that consume reports.
+#[cfg(feature = "test_lib")]
+pub(crate) fn mock_report(
+    part: u8,
+    answer: Option<&str>,
+    samples: &[u64],
+    timed: bool,
+) -> PartReport {
+    let samples: Vec<std::time::Duration> = samples
+        .iter()
+        .map(|&x| std::time::Duration::from_nanos(x))
+        .collect();
+
+    PartReport {
+        part,
+        answer: answer.map(Into::into),
+        stats: Stats::from_samples(&samples).unwrap(),
+        memory: None,
+        timed,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{mock_report, PartReport, StepsReport};
+    use crate::template::stats::MemoryStats;
+
+    fn get_mock_report(answer: Option<&str>) -> PartReport {
+        mock_report(2, answer, &[5_000, 7_000], true)
+    }
+
+    #[test]
+    fn round_trips_reports() {
+        let report = get_mock_report(Some("1,2,3 (74.13µs @ 10 samples)\nline"));
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn round_trips_missing_answers() {
+        let report = get_mock_report(None);
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed.answer, None);
+    }
+
+    #[test]
+    fn writes_single_lines() {
+        let report = get_mock_report(Some("a\nb"));
+        assert_eq!(report.to_json_line().lines().count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_parts() {
+        let _: PartReport = r#"{ "part": 3, "answer": null, "stats": {} }"#.parse().unwrap();
+    }
+
+    #[test]
+    fn round_trips_memory() {
+        let mut report = get_mock_report(Some("1"));
+        report.memory = Some(MemoryStats {
+            blocks: 2,
+            bytes: 64,
+            peak_bytes: 48,
+        });
+        let parsed: PartReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn round_trips_steps() {
+        let report = StepsReport {
+            parse: true,
+            parts: vec![2],
+        };
+        let parsed: StepsReport = report.to_json_line().parse().unwrap();
+        assert_eq!(parsed, report);
+
+        assert!(get_mock_report(None)
+            .to_json_line()
+            .parse::<StepsReport>()
+            .is_err());
+        assert!(report.to_json_line().parse::<PartReport>().is_err());
+    }
+}
@@ -1,41 +1,301 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    bench::{parse_duration, BenchOptions},
+    report::{PartReport, StepsReport, PARSE_PART},
+    stats::format_bytes,
+    Day, Puzzle, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
+use child_commands::OutputLine;
+
+/// The cargo profile that solutions are built with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildProfile {
+    Debug,
+    Release,
+    /// The `dhat` profile, with the `dhat-heap` feature enabled.
+    Dhat,
+    /// The release profile, with the `count-allocs` feature enabled.
+    CountAllocs,
+}
+
+impl BuildProfile {
+    pub fn new(is_release: bool, dhat: bool, count_allocs: bool) -> Self {
+        if dhat {
+            BuildProfile::Dhat
+        } else if count_allocs {
+            BuildProfile::CountAllocs
+        } else if is_release {
+            BuildProfile::Release
+        } else {
+            BuildProfile::Debug
+        }
+    }
+
+    /// Arguments that select this profile for `cargo build` and `cargo run`.
+    pub fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            BuildProfile::Debug => &[],
+            BuildProfile::Release => &["--release"],
+            BuildProfile::Dhat => &["--profile", "dhat", "--features", "dhat-heap"],
+            BuildProfile::CountAllocs => &["--release", "--features", "count-allocs"],
+        }
+    }
+}
+
+/// Wall-clock limits for running a solution binary. Binaries that exceed a limit are killed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit for the whole binary.
+    pub day: Option<Duration>,
+    /// Limit for the parse step and each part.
+    pub part: Option<Duration>,
+}
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+impl Timeouts {
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            day: args.opt_value_from_fn("--timeout", parse_duration)?,
+            part: args.opt_value_from_fn("--part-timeout", parse_duration)?,
+        })
+    }
+}
+
+/// How far a day got.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DayStatus {
+    /// The day has not been scaffolded yet.
+    Missing,
+    /// The solution binary did not compile.
+    BuildFailed,
+    /// The solution binary exited successfully, see its reports for the answers.
+    Ran,
+    /// The solution binary exited with an error, e.g. because a part panicked.
+    Panicked,
+    /// The solution binary exceeded a timeout and was killed.
+    TimedOut,
+    /// The solution binary could not be run. Holds the error.
+    FailedToRun(String),
+}
+
+/// The reports emitted by a single day's solution. Empty if the day has not been solved yet.
+pub struct DayRun {
+    pub day: Day,
+    pub status: DayStatus,
+    pub reports: Vec<PartReport>,
+    /// The steps the solution declared, `None` if it did not get to report them.
+    pub steps: Option<StepsReport>,
+}
 
-    let mut need_space = false;
+impl DayRun {
+    /// Returns `true` if the solution ran and every part it reported has an answer.
+    pub fn is_solved(&self) -> bool {
+        self.status == DayStatus::Ran
+            && self.reports.iter().any(|r| r.part != PARSE_PART)
+            && self
+                .reports
+                .iter()
+                .all(|r| r.part == PARSE_PART || r.answer.is_some())
+    }
+
+    /// Where the solution stopped: in the first declared step without a report, or after its last part.
+    /// Without declared steps, the parse step and both parts are assumed.
+    fn unfinished_step(&self) -> String {
+        let Some(steps) = &self.steps else {
+            return match self.reports.iter().map(|r| r.part).max() {
+                Some(part) if part >= 2 => "after part 2".into(),
+                Some(part) => format!("in part {}", part + 1),
+                None => "in parse step or part 1".into(),
+            };
+        };
+
+        let parse = steps.parse.then_some(PARSE_PART);
+        let unfinished = parse
+            .into_iter()
+            .chain(steps.parts.iter().copied())
+            .find(|part| !self.reports.iter().any(|r| r.part == *part));
+
+        match unfinished {
+            Some(PARSE_PART) => "in parse step".into(),
+            Some(part) => format!("in part {part}"),
+            None => steps.parts.last().map_or_else(
+                || "after parse step".into(),
+                |part| format!("after part {part}"),
+            ),
+        }
+    }
 
+    /// The largest peak heap usage of the parse step and the parts, if memory was measured.
+    pub fn peak_memory(&self) -> Option<u64> {
+        self.reports
+            .iter()
+            .filter_map(|r| r.memory.map(|m| m.peak_bytes))
+            .max()
+    }
+
+    /// Returns the answer reported for a part, if any.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.reports
+            .iter()
+            .find(|r| r.part == part)
+            .and_then(|r| r.answer.as_deref())
+    }
+}
+
+/// The result of running a set of days.
+pub struct MultiRun {
+    pub days: Vec<DayRun>,
+    /// Only present for timed runs.
+    pub timings: Option<Timings>,
+}
+
+/// Runs the solutions of a year. `None` runs the solutions of the configured year.
+///
+/// All solutions are built once up front, then executed directly.
+/// Untimed runs execute up to `jobs` days concurrently, their output is printed grouped by day and in order.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    year: Option<Year>,
+    profile: BuildProfile,
+    is_timed: bool,
+    jobs: usize,
+    timeouts: Timeouts,
+    bench: &BenchOptions,
+) -> MultiRun {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<Puzzle> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .map(|day| Puzzle::new(year, day))
+        .collect();
+
+    // NOTE: timed runs stay serial, concurrent solutions would skew the benchmarks.
+    let jobs = if is_timed {
+        1
+    } else {
+        jobs.clamp(1, puzzles.len().max(1))
+    };
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(puzzles.len());
+    let mut days: Vec<DayRun> = Vec::with_capacity(puzzles.len());
+
+    let executables = child_commands::build_bins(profile);
+
+    let run_day = |puzzle: Puzzle, on_line: &mut dyn FnMut(OutputLine)| {
+        // skip days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return (DayStatus::Missing, vec![]);
+        }
+
+        let Some(executable) = executables.get(&puzzle.to_string()) else {
+            return (DayStatus::BuildFailed, vec![]);
+        };
+
+        child_commands::run_solution(executable, puzzle, is_timed, timeouts, bench, on_line)
+            .unwrap_or_else(|e| (DayStatus::FailedToRun(e.to_string()), vec![]))
+    };
+
+    let mut finish_day = |index: usize, status: DayStatus, lines: Vec<String>| {
+        let day = puzzles[index].day();
+
+        let (steps, reports): (Vec<String>, Vec<String>) = lines
+            .into_iter()
+            .partition(|line| line.parse::<StepsReport>().is_ok());
+
+        match &status {
+            DayStatus::BuildFailed => println!("Build failed."),
+            DayStatus::Panicked => println!("Exited with an error."),
+            DayStatus::TimedOut => println!("Timed out."),
+            DayStatus::FailedToRun(e) => println!("Failed to run: {e}"),
+            DayStatus::Missing | DayStatus::Ran if reports.is_empty() => println!("Not solved."),
+            DayStatus::Missing | DayStatus::Ran => {}
+        }
+
+        if !reports.is_empty() {
+            let val = child_commands::parse_reports(&reports, day);
+            timings.push(val);
+        }
+
+        days.push(DayRun {
+            day,
+            status,
+            reports: reports.iter().filter_map(|l| l.parse().ok()).collect(),
+            steps: steps.first().and_then(|l| l.parse().ok()),
+        });
+    };
+
+    if jobs == 1 {
+        for (index, puzzle) in puzzles.iter().enumerate() {
+            print_header(index, puzzle.day());
+            let (status, reports) = run_day(*puzzle, &mut |line| line.print());
+            finish_day(index, status, reports);
+        }
+    } else {
+        let next = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs {
+                let tx = tx.clone();
+                let (next, puzzles, run_day) = (&next, &puzzles, &run_day);
+
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(puzzle) = puzzles.get(index) else {
+                        break;
+                    };
+
+                    let mut output = vec![];
+                    let (status, reports) = run_day(*puzzle, &mut |line| output.push(line));
+
+                    if tx.send((index, output, status, reports)).is_err() {
+                        break;
+                    }
+                });
             }
-            need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            drop(tx);
+
+            // print each day as soon as all days before it are printed.
+            let mut pending = BTreeMap::new();
+            let mut next_to_print = 0;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            for (index, output, status, reports) in rx {
+                pending.insert(index, (output, status, reports));
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                while let Some((output, status, reports)) = pending.remove(&next_to_print) {
+                    print_header(next_to_print, puzzles[next_to_print].day());
+                    output.iter().for_each(OutputLine::print);
+                    finish_day(next_to_print, status, reports);
+                    next_to_print += 1;
+                }
             }
         });
+    }
+
+    print_summary(&days);
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    let timings = if is_timed {
+        let timings = Timings {
+            data: timings,
+            history: vec![],
+            profiles: vec![],
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -43,7 +303,85 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    MultiRun { days, timings }
+}
+
+/// Print the status of each scaffolded day.
+fn print_summary(days: &[DayRun]) {
+    let days: Vec<&DayRun> = days
+        .iter()
+        .filter(|d| d.status != DayStatus::Missing)
+        .collect();
+
+    if days.is_empty() {
+        return;
+    }
+
+    let (mut solved, mut unsolved, mut panicked, mut timed_out) = (0, 0, 0, 0);
+    let (mut failed_to_build, mut failed_to_run) = (0, 0);
+
+    println!("\n{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("-------");
+
+    for day_run in days {
+        let label = match day_run.peak_memory() {
+            Some(peak) => format!("Day {} (peak {})", day_run.day, format_bytes(peak)),
+            None => format!("Day {}", day_run.day),
+        };
+
+        match day_run.status {
+            DayStatus::Ran if day_run.is_solved() => {
+                solved += 1;
+                println!("{label}: ✔ solved");
+            }
+            DayStatus::Ran => {
+                unsolved += 1;
+                let parts: Vec<String> = day_run
+                    .reports
+                    .iter()
+                    .filter(|r| r.part != PARSE_PART && r.answer.is_none())
+                    .map(|r| format!("part {}", r.part))
+                    .collect();
+                if parts.is_empty() {
+                    println!("{label}: ✖ unsolved");
+                } else {
+                    println!("{label}: ✖ unsolved ({})", parts.join(", "));
+                }
+            }
+            DayStatus::Panicked => {
+                panicked += 1;
+                println!("{label}: ✖ panicked {}", day_run.unfinished_step());
+            }
+            DayStatus::TimedOut => {
+                timed_out += 1;
+                println!("{label}: ✖ timed out {}", day_run.unfinished_step());
+            }
+            DayStatus::BuildFailed => {
+                failed_to_build += 1;
+                println!("{label}: ✖ failed to build");
+            }
+            DayStatus::FailedToRun(_) => {
+                failed_to_run += 1;
+                println!("{label}: ✖ failed to run");
+            }
+            DayStatus::Missing => {}
+        }
     }
+
+    println!(
+        "\n{ANSI_BOLD}{solved} solved, {unsolved} unsolved, {panicked} panicked, {timed_out} timed out, {failed_to_build} failed to build, {failed_to_run} failed to run.{ANSI_RESET}"
+    );
+}
+
+fn print_header(index: usize, day: Day) {
+    if index > 0 {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
 }
 
 #[allow(dead_code)]
@@ -53,6 +391,15 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "the output of the solution could not be read."),
+            Error::IO(e) => write!(f, "io error: {e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -60,47 +407,131 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: Puzzle) -> String {
+    puzzle.bin_path()
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the reports they emit.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{BuildProfile, DayStatus, Error, HashMap, PathBuf, Timeouts};
+    use crate::template::{
+        bench::BenchOptions,
+        machine::DEFAULT_PROFILE,
+        report::{PartReport, ENV_REPORT_FILE, PARSE_PART},
+        Day, Puzzle,
+    };
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Command, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// How often running binaries are checked against their timeouts.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// A line of output of a solution.
+    pub enum OutputLine {
+        Stdout(String),
+        Stderr(String),
+    }
 
-        if is_release {
-            args.push("--release");
+    impl OutputLine {
+        pub fn print(&self) {
+            match self {
+                OutputLine::Stdout(line) => println!("{line}"),
+                OutputLine::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+
+    /// Build all solution binaries with a single cargo invocation.
+    /// Returns the paths of the executables that were built, by binary name.
+    ///
+    /// Compiler errors are printed to stderr. Binaries that fail to compile are missing from the result.
+    pub fn build_bins(profile: BuildProfile) -> HashMap<String, PathBuf> {
+        let output = Command::new("cargo")
+            .args([
+                "build",
+                "--quiet",
+                "--bins",
+                "--keep-going",
+                "--message-format=json-render-diagnostics",
+            ])
+            .args(profile.cargo_args())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output();
+
+        match output {
+            Ok(output) => parse_build_output(&String::from_utf8_lossy(&output.stdout)),
+            Err(e) => {
+                eprintln!("Failed to call cargo: {e}");
+                HashMap::new()
+            }
         }
+    }
+
+    /// Collects the executables from the JSON messages printed by `cargo build`.
+    pub fn parse_build_output(stdout: &str) -> HashMap<String, PathBuf> {
+        stdout
+            .lines()
+            .filter_map(|line| {
+                let json = JsonValue::from_str(line).ok()?;
+                let json = json.get::<HashMap<String, JsonValue>>()?;
+
+                if json.get("reason")?.get::<String>()? != "compiler-artifact" {
+                    return None;
+                }
+
+                let name = json
+                    .get("target")?
+                    .get::<HashMap<String, JsonValue>>()?
+                    .get("name")?
+                    .get::<String>()?;
+
+                let executable = json.get("executable")?.get::<String>()?;
+
+                Some((name.clone(), PathBuf::from(executable)))
+            })
+            .collect()
+    }
+
+    /// Run a prebuilt solution binary. Returns how it exited and the report lines emitted by the solution.
+    ///
+    /// Every line of output is passed to `on_line` in the order it was received.
+    /// The binary is killed when it exceeds one of the `timeouts`.
+    pub fn run_solution(
+        executable: &Path,
+        puzzle: Puzzle,
+        is_timed: bool,
+        timeouts: Timeouts,
+        bench: &BenchOptions,
+        on_line: &mut dyn FnMut(OutputLine),
+    ) -> Result<(DayStatus, Vec<String>), Error> {
+        let mut args: Vec<String> = vec![];
 
         if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            // mirror `--time` flag and benchmark options to child invocations.
+            args.push("--time".into());
+            args.extend(bench.to_args());
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        let report_path =
+            env::temp_dir().join(format!("aoc-report-{}-{puzzle}.jsonl", process::id()));
+        // NOTE: the runner appends to the report file, make sure that we start from scratch.
+        let _ = fs::remove_file(&report_path);
 
-        let mut cmd = Command::new("cargo")
+        // spawn child command with piped stdout/stderr and forward output to `on_line`.
+        let mut cmd = Command::new(executable)
             .args(&args)
+            .env(ENV_REPORT_FILE, &report_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -108,87 +539,118 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let (tx, rx) = mpsc::channel();
+        let stderr_tx = tx.clone();
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let stdout_thread = thread::spawn(move || {
+            for line in stdout.lines() {
+                let _ = tx.send(OutputLine::Stdout(line.unwrap()));
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let stderr_thread = thread::spawn(move || {
+            for line in stderr.lines() {
+                let _ = stderr_tx.send(OutputLine::Stderr(line.unwrap()));
+            }
+        });
+
+        let started = Instant::now();
+        let mut step_started = started;
+        let mut finished_steps = 0;
+        let mut timed_out = false;
+
+        // NOTE: the channel closes once both pipes are closed, i.e. when the child exits or is killed.
+        loop {
+            match rx.recv_timeout(POLL_INTERVAL) {
+                Ok(line) => on_line(line),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            if timed_out || (timeouts.day.is_none() && timeouts.part.is_none()) {
+                continue;
+            }
+
+            // every finished step (parse or part) appends a line to the report file.
+            let steps = read_reports(&report_path).len();
+            if steps != finished_steps {
+                finished_steps = steps;
+                step_started = Instant::now();
+            }
+
+            if timeouts.day.is_some_and(|t| started.elapsed() > t)
+                || timeouts.part.is_some_and(|t| step_started.elapsed() > t)
+            {
+                timed_out = true;
+                let _ = cmd.kill();
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
+        let exit_status = cmd.wait()?;
 
-        Ok(output)
-    }
+        let reports = read_reports(&report_path);
+        let _ = fs::remove_file(&report_path);
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+        let status = if timed_out {
+            DayStatus::TimedOut
+        } else if exit_status.success() {
+            DayStatus::Ran
+        } else {
+            DayStatus::Panicked
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        Ok((status, reports))
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    fn read_reports(path: &Path) -> Vec<String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => contents.lines().map(String::from).collect(),
+            Err(_) => vec![],
+        }
+    }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+    /// Collects the timings of a day from the report lines emitted by its solution.
+    pub fn parse_reports(reports: &[String], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day, DEFAULT_PROFILE);
+
+        reports
+            .iter()
+            .filter_map(|l| match l.parse::<PartReport>() {
+                Ok(report) => Some(report),
+                Err(e) => {
+                    eprintln!("Could not parse report \"{l}\": {e}");
+                    None
+                }
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            // NOTE: unsolved parts and untimed runs are not benchmarks.
+            .filter(|report| (report.part == PARSE_PART || report.answer.is_some()) && report.timed)
+            .for_each(|report| {
+                let timing_str = format!("{:.1?}", report.stats.mean);
+
+                if report.part == PARSE_PART {
+                    timings.parse = Some(timing_str);
+                    timings.parse_stats = Some(report.stats);
+                    timings.parse_memory = report.memory;
+                } else if report.part == 1 {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats = Some(report.stats);
+                    timings.part_1_memory = report.memory;
+                } else {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats = Some(report.stats);
+                    timings.part_2_memory = report.memory;
                 }
 
+                #[allow(clippy::cast_precision_loss)]
+                let nanos = report.stats.mean.as_nanos() as f64;
                 timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,47 +667,100 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_build_output, parse_reports};
+        use crate::template::run_multi::{DayRun, DayStatus};
+        use std::{path::PathBuf, time::Duration};
+
+        use crate::{
+            day,
+            template::{
+                report::{mock_report, PartReport, StepsReport},
+                stats::MemoryStats,
+            },
+        };
 
-        use crate::day;
+        fn report(part: u8, answer: Option<&str>, samples: &[u64]) -> String {
+            mock_report(part, answer, samples, true).to_json_line()
+        }
 
         #[test]
         fn parses_execution_times() {
-            let res = parse_exec_time(
+            let res = parse_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), &[74, 74]),
+                    report(2, Some("10"), &[74_130_000, 74_130_000]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn parses_parse_times() {
+            let res = parse_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    report(0, None, &[1000, 1000]),
+                    report(1, Some("0"), &[2000, 2000]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.total_nanos, 3000_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.parse_stats.unwrap().samples, 2);
+            assert_eq!(res.part_1.unwrap(), "2.0µs");
+        }
+
+        #[test]
+        fn parses_execution_stats() {
+            let res = parse_reports(&[report(1, Some("0"), &[1000, 2000, 3000])], day!(1));
+            let stats = res.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 3);
+            assert_eq!(stats.mean, Duration::from_micros(2));
+            assert_eq!(stats.min, Duration::from_micros(1));
+            assert_eq!(stats.max, Duration::from_micros(3));
+            assert!(res.part_2_stats.is_none());
+        }
+
+        #[test]
+        fn parses_with_patterns_in_answer() {
+            let res = parse_reports(
+                &[
+                    report(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), &[2, 2]),
+                    report(2, Some("10s\n(100ms @ 1 samples)"), &[100, 100]),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 102_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0ns");
+            assert_eq!(res.part_2.unwrap(), "100.0ns");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
+            let res = parse_reports(
+                &[report(1, None, &[10, 10]), report(2, None, &[10, 10])],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_single_sample_benchmarks() {
+            let res = parse_reports(&[report(1, Some("1"), &[10])], day!(1));
+            assert_eq!(res.part_1.unwrap(), "10.0ns");
+            assert_eq!(res.part_1_stats.unwrap().samples, 1);
+        }
+
+        #[test]
+        fn skips_untimed_and_malformed_reports() {
+            let res = parse_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    mock_report(1, Some("1"), &[10, 10], false).to_json_line(),
+                    "Part 2: 10 (1ms @ 5 samples)".into(),
                 ],
                 day!(1),
             );
@@ -253,5 +768,122 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_build_output() {
+            let stdout = [
+                r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code","kind":["lib"]},"executable":null}"#,
+                r#"{"reason":"compiler-artifact","target":{"name":"01","kind":["bin"]},"executable":"/target/release/01"}"#,
+                r#"{"reason":"compiler-message","target":{"name":"02","kind":["bin"]},"message":{}}"#,
+                r#"{"reason":"build-finished","success":false}"#,
+            ]
+            .join("\n");
+
+            let executables = parse_build_output(&stdout);
+            assert_eq!(executables.len(), 1);
+            assert_eq!(
+                executables.get("01"),
+                Some(&PathBuf::from("/target/release/01"))
+            );
+        }
+
+        #[test]
+        fn classifies_day_runs() {
+            let day_run = |status: DayStatus, reports: &[String]| DayRun {
+                day: day!(1),
+                status,
+                reports: reports.iter().map(|r| r.parse().unwrap()).collect(),
+                steps: None,
+            };
+
+            let solved = day_run(
+                DayStatus::Ran,
+                &[report(0, None, &[10]), report(1, Some("1"), &[10])],
+            );
+            assert!(solved.is_solved());
+
+            let unsolved = day_run(
+                DayStatus::Ran,
+                &[report(1, Some("1"), &[10]), report(2, None, &[10])],
+            );
+            assert!(!unsolved.is_solved());
+            assert!(!day_run(DayStatus::Ran, &[]).is_solved());
+            assert!(!day_run(DayStatus::FailedToRun("io error".into()), &[]).is_solved());
+
+            let timed_out = day_run(DayStatus::TimedOut, &[report(1, Some("1"), &[10])]);
+            assert!(!timed_out.is_solved());
+            assert_eq!(timed_out.unfinished_step(), "in part 2");
+            assert_eq!(
+                day_run(DayStatus::Panicked, &[]).unfinished_step(),
+                "in parse step or part 1"
+            );
+            assert_eq!(
+                day_run(DayStatus::Panicked, &[report(2, Some("1"), &[10])]).unfinished_step(),
+                "after part 2"
+            );
+        }
+
+        #[test]
+        fn names_unfinished_declared_steps() {
+            let day_run = |parse: bool, parts: &[u8], reports: &[String]| DayRun {
+                day: day!(1),
+                status: DayStatus::Panicked,
+                reports: reports.iter().map(|r| r.parse().unwrap()).collect(),
+                steps: Some(StepsReport {
+                    parse,
+                    parts: parts.to_vec(),
+                }),
+            };
+
+            assert_eq!(day_run(false, &[2], &[]).unfinished_step(), "in part 2");
+            assert_eq!(day_run(true, &[2], &[]).unfinished_step(), "in parse step");
+            assert_eq!(
+                day_run(true, &[1, 2], &[report(0, None, &[10])]).unfinished_step(),
+                "in part 1"
+            );
+            assert_eq!(
+                day_run(false, &[1], &[report(1, Some("1"), &[10])]).unfinished_step(),
+                "after part 1"
+            );
+            assert_eq!(
+                day_run(
+                    false,
+                    &[1, 2],
+                    &[report(1, Some("1"), &[10]), report(2, Some("2"), &[10])]
+                )
+                .unfinished_step(),
+                "after part 2"
+            );
+        }
+
+        #[test]
+        fn reports_peak_memory() {
+            let report = |part: u8, peak_bytes: Option<u64>| PartReport {
+                memory: peak_bytes.map(|peak_bytes| MemoryStats {
+                    blocks: 1,
+                    bytes: peak_bytes,
+                    peak_bytes,
+                }),
+                ..mock_report(part, Some("1"), &[10], false)
+            };
+
+            let day_run = |reports: Vec<PartReport>| DayRun {
+                day: day!(1),
+                status: DayStatus::Ran,
+                reports,
+                steps: None,
+            };
+
+            assert_eq!(
+                day_run(vec![
+                    report(0, Some(512)),
+                    report(1, Some(2048)),
+                    report(2, Some(64))
+                ])
+                .peak_memory(),
+                Some(2048)
+            );
+            assert_eq!(day_run(vec![report(1, None)]).peak_memory(), None);
+        }
     }
 }
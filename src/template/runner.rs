@@ -1,65 +1,175 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers::Answers;
+use crate::template::bench::{BenchConfig, BenchOptions};
+use crate::template::report::{PartReport, StepsReport, PARSE_PART};
+use crate::template::stats::{MemoryStats, Stats};
+use crate::template::submissions::SubmissionLog;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, unix_timestamp, Answer, Puzzle, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Report the steps of a solution before running them, so that `run_multi` can name the step that did not finish.
+pub fn report_steps(parse: bool, parts: &[u8]) {
+    let report = StepsReport {
+        parse,
+        parts: parts.to_vec(),
+    };
+
+    if let Err(e) = report.write_to_env_file() {
+        eprintln!("Failed to write report: {e}");
+    }
+}
+
+/// Run a solution part. Its result is normalised to an [`Answer`], which is printed, reported and submitted.
+pub fn run_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, memory, timed) = run_timed(
+        |input| func(input).map(Into::into),
+        input,
+        &format!("part-{part}"),
+        |result| {
+            print_result(result, &part_str, "");
+        },
+    );
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &(format_stats(&stats) + &format_memory(memory)),
+    );
+
+    let report = PartReport {
+        part,
+        answer: result.as_ref().map(Answer::canonical),
+        stats,
+        memory,
+        timed,
+    };
+
+    if let Err(e) = report.write_to_env_file() {
+        eprintln!("Failed to write report: {e}");
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(&result, puzzle, part);
     }
 }
 
+/// Run the shared parse step of a solution and return its output, which is then passed to each part.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str) -> P {
+    let (parsed, stats, memory, timed) = run_timed(func, input, "parse", |_| print!("Parse:"));
+
+    print!("\r");
+    println!("Parse:{}{}", format_stats(&stats), format_memory(memory));
+
+    let report = PartReport {
+        part: PARSE_PART,
+        answer: None,
+        stats,
+        memory,
+        timed,
+    };
+
+    if let Err(e) = report.write_to_env_file() {
+        eprintln!("Failed to write report: {e}");
+    }
+
+    parsed
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `dhat-heap` feature, the first run is profiled and its heap usage is returned.
+/// The profile of each step is saved to `dhat-heap-<step>.json`.
+/// With the `count-allocs` feature, the allocations of the first run are counted instead.
+///
+/// The returned flag is `true` if the function was benched, i.e. its stats are a benchmark, whatever the number of samples.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    #[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))] step: &str,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
+) -> (T, Stats, Option<MemoryStats>, bool) {
+    let (result, base_time, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = dhat::Profiler::builder()
+            .file_name(format!("dhat-heap-{step}.json"))
+            .build();
+
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        crate::template::allocator::reset();
+
+        let timer = Instant::now();
+        let result = func(input);
+        let base_time = timer.elapsed();
 
-        func(input)
+        (result, base_time, heap_stats())
     };
-    let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let timed = std::env::args().any(|x| x == "--time");
+
+    let timers = if timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        vec![base_time]
     };
 
-    (result, run.0, run.1)
+    // NOTE: there is always at least one sample.
+    (result, Stats::from_samples(&timers).unwrap(), memory, timed)
+}
+
+/// Heap usage since the profiler started.
+#[cfg(feature = "dhat-heap")]
+fn heap_stats() -> Option<MemoryStats> {
+    let stats = dhat::HeapStats::get();
+
+    Some(MemoryStats {
+        blocks: stats.total_blocks,
+        bytes: stats.total_bytes,
+        peak_bytes: stats.max_bytes as u64,
+    })
+}
+
+/// Allocations since the counters were reset.
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+fn heap_stats() -> Option<MemoryStats> {
+    Some(crate::template::allocator::stats())
+}
+
+#[cfg(not(any(feature = "dhat-heap", feature = "count-allocs")))]
+fn heap_stats() -> Option<MemoryStats> {
+    None
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Vec<Duration> {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let config = bench_config();
+
+    for _ in 0..config.warmup {
+        black_box(func(black_box(input.clone())));
+    }
+
+    let bench_iterations = config.iterations(base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,50 +181,74 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    timers
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Read the benchmark configuration forwarded by `cargo time` / `cargo solve`, falling back to the environment.
+fn bench_config() -> BenchConfig {
+    let mut args = pico_args::Arguments::from_env();
+
+    let config = BenchOptions::from_args(&mut args)
+        .map_err(|e| e.to_string())
+        .and_then(|cli| {
+            let env = BenchOptions::from_env().map_err(|e| e.to_string())?;
+            cli.or(env).resolve().map_err(|e| e.to_string())
+        });
+
+    match config {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid benchmark options: {e}");
+            process::exit(1);
+        }
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_stats(stats: &Stats) -> String {
+    let Stats {
+        samples,
+        mean,
+        min,
+        median,
+        p95,
+        max,
+        std_dev,
+        outliers,
+    } = stats;
+
+    if *samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " ({mean:.1?} @ {samples} samples) [min {min:.1?} · median {median:.1?} · p95 {p95:.1?} · max {max:.1?} · σ {std_dev:.1?} · {outliers} outliers]"
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(String::new, |memory| format!(" [{memory}]"))
+}
+
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
+            let canonical = result.canonical();
+            let str = if canonical.contains('\n') {
+                format!("{part}: ▼ {duration_str}")
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
+                format!("{part}: {ANSI_BOLD}{canonical}{ANSI_RESET}{duration_str}")
+            };
+
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+                // NOTE: art is shown below the answer, so that letters that were read can be verified.
+                if let Answer::Art { art, .. } = result {
+                    println!("{}", art.trim_end());
                 }
             }
         }
@@ -131,12 +265,15 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+///  2. aoc-cli is installed (or the native client is configured).
+///
+/// Answers that previous submissions have ruled out are not sent, see [`SubmissionLog::check`].
+/// Accepted answers are recorded in the answer registry.
+fn submit_result(
+    result: &Answer,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +296,60 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if !result.is_submittable() {
+        eprintln!("Not submitting: the answer is empty or ASCII art that could not be read.");
+        return None;
+    }
+
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.canonical();
+    let mut log = match SubmissionLog::read_from_file(puzzle) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Not submitting {answer}: the submission log can not be checked, {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = log.check(part, &answer, unix_timestamp()) {
+        eprintln!("Not submitting {answer}: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let output = aoc_cli::submit(puzzle, part, &answer);
+
+    let response = match &output {
+        Ok(response) => Some(response.as_str()),
+        Err(aoc_cli::AocCommandError::RateLimited(message)) => Some(message.as_str()),
+        Err(_) => None,
+    };
+
+    if let Some(response) = response {
+        log.record(part, &answer, response, unix_timestamp());
+
+        if let Err(e) = log.store_file() {
+            eprintln!("Failed to store submission: {e}");
+        }
+    }
+
+    if output.as_deref().is_ok_and(aoc_cli::is_correct_answer) {
+        let stored = Answers::read_from_file(puzzle.explicit_year()).and_then(|mut answers| {
+            answers.set(puzzle.day(), part, &answer);
+            answers
+                .store_file(puzzle.explicit_year())
+                .map_err(|e| e.to_string())
+        });
+
+        match stored {
+            Ok(()) => println!("Stored accepted answer for {puzzle}, part {part}."),
+            Err(e) => eprintln!("Failed to store accepted answer: {e}"),
+        }
+    }
+
+    Some(output)
 }
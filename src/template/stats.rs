@@ -0,0 +1,284 @@
+//! Summary statistics over the samples collected by the benchmark runner.
+
+use std::{collections::HashMap, fmt::Display, time::Duration};
+use tinyjson::JsonValue;
+
+/// Describes the distribution of a set of benchmark samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the 1.5 * IQR fences.
+    pub outliers: u128,
+}
+
+/// Heap usage of a single run, as measured by the allocator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of allocations.
+    pub blocks: u64,
+    /// Total number of allocated bytes.
+    pub bytes: u64,
+    /// Maximum number of bytes that were allocated at once.
+    pub peak_bytes: u64,
+}
+
+impl Stats {
+    /// Summarizes a set of samples. Returns `None` if no samples were collected.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let len = sorted.len() as u128;
+        let mean = sorted.iter().sum::<u128>() / len;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|&x| (x as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / len as f64;
+
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let fence = (q3 - q1) * 3 / 2;
+        let lower = q1.saturating_sub(fence);
+        let upper = q3 + fence;
+
+        Some(Self {
+            samples: len,
+            mean: from_nanos(mean),
+            min: from_nanos(sorted[0]),
+            median: from_nanos(percentile(&sorted, 50)),
+            p95: from_nanos(percentile(&sorted, 95)),
+            max: from_nanos(sorted[sorted.len() - 1]),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            std_dev: from_nanos(variance.sqrt().round() as u128),
+            outliers: sorted.iter().filter(|&&x| x < lower || x > upper).count() as u128,
+        })
+    }
+}
+
+/// Nearest-rank percentile of an already sorted slice.
+fn percentile(sorted: &[u128], pct: usize) -> u128 {
+    let rank = (pct * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in {} allocations · peak {}",
+            format_bytes(self.bytes),
+            self.blocks,
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Stats> for JsonValue {
+    fn from(value: Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, nanos: u128| {
+            map.insert(key.into(), JsonValue::Number(nanos as f64));
+        };
+
+        insert("samples", value.samples);
+        insert("mean_nanos", value.mean.as_nanos());
+        insert("min_nanos", value.min.as_nanos());
+        insert("median_nanos", value.median.as_nanos());
+        insert("p95_nanos", value.p95.as_nanos());
+        insert("max_nanos", value.max.as_nanos());
+        insert("std_dev_nanos", value.std_dev.as_nanos());
+        insert("outliers", value.outliers);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u128)
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation)]
+        let get_duration = |key: &str| get(key).map(|v| Duration::from_nanos(v as u64));
+
+        Ok(Stats {
+            samples: get("samples")?,
+            mean: get_duration("mean_nanos")?,
+            min: get_duration("min_nanos")?,
+            median: get_duration("median_nanos")?,
+            p95: get_duration("p95_nanos")?,
+            max: get_duration("max_nanos")?,
+            std_dev: get_duration("std_dev_nanos")?,
+            outliers: get("outliers")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<MemoryStats> for JsonValue {
+    fn from(value: MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, value: u64| {
+            map.insert(key.into(), JsonValue::Number(value as f64));
+        };
+
+        insert("blocks", value.blocks);
+        insert("bytes", value.bytes);
+        insert("peak_bytes", value.peak_bytes);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            blocks: get("blocks")?,
+            bytes: get("bytes")?,
+            peak_bytes: get("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, MemoryStats, Stats};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&x| Duration::from_nanos(x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(42));
+        assert_eq!(stats.min, Duration::from_nanos(42));
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.p95, Duration::from_nanos(42));
+        assert_eq!(stats.max, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 4, 2, 3])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.median, Duration::from_nanos(3));
+        assert_eq!(stats.p95, Duration::from_nanos(5));
+        assert_eq!(stats.max, Duration::from_nanos(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_nanos(11));
+        assert_eq!(stats.max, Duration::from_nanos(500));
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn round_trips_memory_stats() {
+        let memory = MemoryStats {
+            blocks: 3,
+            bytes: 2048,
+            peak_bytes: 1024,
+        };
+        let json = tinyjson::JsonValue::from(memory);
+        assert_eq!(MemoryStats::try_from(&json).unwrap(), memory);
+        assert_eq!(
+            memory.to_string(),
+            "2.0 KiB in 3 allocations · peak 1.0 KiB"
+        );
+    }
+}
@@ -0,0 +1,475 @@
+//! Per-day log of submitted answers, used to avoid submissions that are known to be wrong.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{self, Error},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Puzzle;
+
+static SUBMISSIONS_DIR: &str = "submissions";
+
+/// The response of the site to a submission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was not checked because we had to wait.
+    RateLimited,
+    Unknown,
+}
+
+impl Verdict {
+    /// Classifies the response message of a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("answer too recently") {
+            Verdict::RateLimited
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("not the right answer") {
+            Verdict::Wrong
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    fn is_rejection(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate_limited" => Ok(Verdict::RateLimited),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: Verdict,
+}
+
+/// All submissions for a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub puzzle: Puzzle,
+    pub submissions: Vec<Submission>,
+    /// Seconds since the unix epoch before which the site will not accept another answer.
+    pub wait_until: Option<u64>,
+}
+
+/// The reason a submission was not sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionRefusal {
+    AlreadyCorrect(String),
+    AlreadyRejected(Verdict),
+    NotBelow(String),
+    NotAbove(String),
+    Cooldown(u64),
+}
+
+impl Display for SubmissionRefusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionRefusal::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            SubmissionRefusal::AlreadyRejected(verdict) => write!(
+                f,
+                "this answer was already submitted and rejected ({}).",
+                verdict.as_str().replace('_', " ")
+            ),
+            SubmissionRefusal::NotBelow(answer) => {
+                write!(f, "{answer} was too high, this answer is not lower.")
+            }
+            SubmissionRefusal::NotAbove(answer) => {
+                write!(f, "{answer} was too low, this answer is not higher.")
+            }
+            SubmissionRefusal::Cooldown(secs) => {
+                write!(f, "please wait {secs}s before submitting again.")
+            }
+        }
+    }
+}
+
+impl SubmissionLog {
+    pub fn new(puzzle: Puzzle) -> Self {
+        Self {
+            puzzle,
+            submissions: vec![],
+            wait_until: None,
+        }
+    }
+
+    fn path(puzzle: Puzzle) -> String {
+        puzzle.data_path(SUBMISSIONS_DIR, &format!("{}.json", puzzle.day()))
+    }
+
+    /// Rehydrate the log from a JSON file. If not present, returns an empty log.
+    /// Fails if the file can not be read or parsed, so that the recorded submissions are not overwritten.
+    pub fn read_from_file(puzzle: Puzzle) -> Result<Self, String> {
+        let path = Self::path(puzzle);
+
+        match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json, puzzle)
+                .map_err(|e| format!("failed to parse \"{path}\": {e}")),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::new(puzzle)),
+            Err(e) => Err(format!("failed to read \"{path}\": {e}")),
+        }
+    }
+
+    /// Dehydrate the log to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = Self::path(self.puzzle);
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Checks whether an answer should be submitted, based on previous submissions.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), SubmissionRefusal> {
+        let previous = self.submissions.iter().filter(|s| s.part == part);
+
+        for submission in previous.clone() {
+            if submission.verdict == Verdict::Correct {
+                return Err(SubmissionRefusal::AlreadyCorrect(submission.answer.clone()));
+            }
+
+            if submission.answer == answer && submission.verdict.is_rejection() {
+                return Err(SubmissionRefusal::AlreadyRejected(submission.verdict));
+            }
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            for submission in previous {
+                let Ok(other) = submission.answer.parse::<i128>() else {
+                    continue;
+                };
+
+                match submission.verdict {
+                    Verdict::TooHigh if value >= other => {
+                        return Err(SubmissionRefusal::NotBelow(submission.answer.clone()))
+                    }
+                    Verdict::TooLow if value <= other => {
+                        return Err(SubmissionRefusal::NotAbove(submission.answer.clone()))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        match self.wait_until {
+            Some(wait_until) if wait_until > now => {
+                Err(SubmissionRefusal::Cooldown(wait_until - now))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a submission and the cooldown announced in the response.
+    pub fn record(&mut self, part: u8, answer: &str, response: &str, now: u64) -> Verdict {
+        let verdict = Verdict::from_response(response);
+
+        self.submissions.push(Submission {
+            part,
+            answer: answer.into(),
+            timestamp: now,
+            verdict,
+        });
+
+        self.wait_until = parse_wait(response).map(|secs| now + secs);
+
+        verdict
+    }
+}
+
+/// Extracts the time to wait from a response, e.g. "You have 1m 5s left to wait" or "Please wait one minute".
+fn parse_wait(response: &str) -> Option<u64> {
+    if let Some(end) = response.find(" left to wait") {
+        let start = response[..end].rfind("You have ")? + "You have ".len();
+        return response[start..end]
+            .split_whitespace()
+            .map(|part| {
+                if let Some(m) = part.strip_suffix('m') {
+                    m.parse::<u64>().ok().map(|m| m * 60)
+                } else {
+                    part.strip_suffix('s')?.parse::<u64>().ok()
+                }
+            })
+            .sum();
+    }
+
+    let start = response.find("wait ")? + "wait ".len();
+    let mut words = response[start..].split_whitespace();
+    let amount = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "five" => 5,
+        "ten" => 10,
+        s => s.parse().ok()?,
+    };
+
+    match words.next()? {
+        s if s.starts_with("minute") => Some(amount * 60),
+        s if s.starts_with("second") => Some(amount),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day().to_string()),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "wait_until".into(),
+            value
+                .wait_until
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl SubmissionLog {
+    fn from_json(s: &str, puzzle: Puzzle) -> Result<Self, String> {
+        let json = JsonValue::from_str(s).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let wait_until = json
+            .get("wait_until")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        let submissions = json
+            .get("submissions")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `json.submissions` to be an array.")?
+            .iter()
+            .map(Submission::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            puzzle,
+            submissions,
+            wait_until,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            part,
+            answer: answer.clone(),
+            timestamp,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait, SubmissionLog, SubmissionRefusal, Verdict};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    const TOO_HIGH: &str = "That's not the right answer; your answer is too high. Please wait one minute before trying again.";
+    const TOO_LOW: &str = "That's not the right answer; your answer is too low. Please wait one minute before trying again.";
+    const WRONG: &str = "That's not the right answer. Please wait 5 minutes before trying again.";
+    const TOO_RECENT: &str = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.";
+    const CORRECT: &str = "That's the right answer! You are one gold star closer.";
+
+    #[test]
+    fn classifies_responses() {
+        assert_eq!(Verdict::from_response(TOO_HIGH), Verdict::TooHigh);
+        assert_eq!(Verdict::from_response(TOO_LOW), Verdict::TooLow);
+        assert_eq!(Verdict::from_response(WRONG), Verdict::Wrong);
+        assert_eq!(Verdict::from_response(TOO_RECENT), Verdict::RateLimited);
+        assert_eq!(Verdict::from_response(CORRECT), Verdict::Correct);
+        assert_eq!(Verdict::from_response("???"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(parse_wait(TOO_HIGH), Some(60));
+        assert_eq!(parse_wait(WRONG), Some(300));
+        assert_eq!(parse_wait(TOO_RECENT), Some(65));
+        assert_eq!(parse_wait("You have 34s left to wait."), Some(34));
+        assert_eq!(parse_wait(CORRECT), None);
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let mut log = SubmissionLog::new(day!(1).into());
+        log.record(1, "42", WRONG, 0);
+        assert_eq!(
+            log.check(1, "42", 1000),
+            Err(SubmissionRefusal::AlreadyRejected(Verdict::Wrong))
+        );
+        assert_eq!(log.check(1, "43", 1000), Ok(()));
+        assert_eq!(log.check(2, "42", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_of_bounds() {
+        let mut log = SubmissionLog::new(day!(1).into());
+        log.record(1, "100", TOO_HIGH, 0);
+        log.record(1, "10", TOO_LOW, 0);
+
+        assert_eq!(
+            log.check(1, "150", 1000),
+            Err(SubmissionRefusal::NotBelow("100".into()))
+        );
+        assert_eq!(
+            log.check(1, "5", 1000),
+            Err(SubmissionRefusal::NotAbove("10".into()))
+        );
+        assert_eq!(log.check(1, "50", 1000), Ok(()));
+        assert_eq!(log.check(1, "abc", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_during_cooldown() {
+        let mut log = SubmissionLog::new(day!(1).into());
+        log.record(1, "42", TOO_RECENT, 100);
+        assert_eq!(
+            log.check(1, "42", 120),
+            Err(SubmissionRefusal::Cooldown(45))
+        );
+        assert_eq!(log.check(1, "42", 165), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut log = SubmissionLog::new(day!(1).into());
+        log.record(2, "42", CORRECT, 0);
+        assert_eq!(
+            log.check(2, "43", 1000),
+            Err(SubmissionRefusal::AlreadyCorrect("42".into()))
+        );
+    }
+
+    #[test]
+    fn round_trips_logs() {
+        let mut log = SubmissionLog::new(day!(3).into());
+        log.record(1, "100", TOO_HIGH, 10);
+        log.record(1, "50", CORRECT, 200);
+        log.record(2, "7", TOO_HIGH, 300);
+
+        let json = JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(
+            SubmissionLog::from_json(&json, day!(3).into()).unwrap(),
+            log
+        );
+    }
+}
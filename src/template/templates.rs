@@ -0,0 +1,165 @@
+//! Named templates for new solutions, read from `templates/<name>.txt`.
+//!
+//! Templates contain placeholders like `%DAY_NUMBER%`, see `placeholder` for the full list.
+
+use std::{fmt::Display, fs, io};
+
+use crate::template::Puzzle;
+
+pub const TEMPLATES_DIR: &str = "templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    /// No template with this name exists. Holds the names of the available templates.
+    NotFound(String, Vec<String>),
+    UnknownPlaceholder(String),
+    IO(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound(name, available) => write!(
+                f,
+                "template \"{name}\" not found in \"{TEMPLATES_DIR}/\", available templates: {}.",
+                available.join(", ")
+            ),
+            TemplateError::UnknownPlaceholder(name) => {
+                write!(f, "unknown placeholder `%{name}%`.")
+            }
+            TemplateError::IO(e) => write!(f, "io error: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TemplateError {
+    fn from(e: io::Error) -> Self {
+        TemplateError::IO(e)
+    }
+}
+
+/// The names of all templates in the templates directory, sorted.
+pub fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            name.to_str()?.strip_suffix(".txt").map(String::from)
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+/// Read a template and fill in its placeholders for a puzzle.
+pub fn render_template(name: &str, puzzle: Puzzle) -> Result<String, TemplateError> {
+    let path = format!("{TEMPLATES_DIR}/{name}.txt");
+
+    let template = match fs::read_to_string(path) {
+        Ok(template) => template,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(TemplateError::NotFound(
+                name.to_string(),
+                available_templates(),
+            ))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    render(&template, puzzle)
+}
+
+/// Replace all `%NAME%` placeholders. Percent signs that do not enclose a placeholder name are kept.
+fn render(template: &str, puzzle: Puzzle) -> Result<String, TemplateError> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('%') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let len = after
+            .find(|c: char| !(c.is_ascii_uppercase() || c == '_'))
+            .unwrap_or(after.len());
+
+        if len > 0 && after[len..].starts_with('%') {
+            let name = &after[..len];
+            let value = placeholder(name, puzzle)
+                .ok_or_else(|| TemplateError::UnknownPlaceholder(name.to_string()))?;
+            rendered.push_str(&value);
+            rest = &after[len + 1..];
+        } else {
+            rendered.push('%');
+            rest = after;
+        }
+    }
+
+    rendered.push_str(rest);
+    Ok(rendered)
+}
+
+/// The value of a placeholder:
+/// - `%DAY_NUMBER%`: the day without padding, e.g. `1`.
+/// - `%DAY%`: the padded day, e.g. `01`.
+/// - `%YEAR%`: the year of the puzzle, e.g. `2024`.
+/// - `%PUZZLE%`: the name of the solution binary, e.g. `01` or `2023-01`.
+/// - `%URL%`: the link to the puzzle description.
+fn placeholder(name: &str, puzzle: Puzzle) -> Option<String> {
+    let day = puzzle.day();
+    let year = puzzle.year().map(|y| y.to_string()).unwrap_or_default();
+
+    match name {
+        "DAY_NUMBER" => Some(day.into_inner().to_string()),
+        "DAY" => Some(day.to_string()),
+        "YEAR" => Some(year),
+        "PUZZLE" => Some(puzzle.to_string()),
+        "URL" => Some(format!(
+            "https://adventofcode.com/{year}/day/{}",
+            day.into_inner()
+        )),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, TemplateError};
+    use crate::day;
+    use crate::template::{Puzzle, Year};
+
+    #[test]
+    fn renders_placeholders() {
+        let puzzle = Puzzle::new(Year::new(2015), day!(8));
+
+        let rendered = render(
+            "solution!(%DAY_NUMBER%); // %DAY% %YEAR% %PUZZLE% %URL%",
+            puzzle,
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "solution!(8); // 08 2015 2015-08 https://adventofcode.com/2015/day/8"
+        );
+    }
+
+    #[test]
+    fn keeps_other_percent_signs() {
+        let puzzle = Puzzle::new(Year::new(2015), day!(8));
+
+        assert_eq!(
+            render("a % b %DAY%% 100%", puzzle).unwrap(),
+            "a % b 08% 100%"
+        );
+        assert!(matches!(
+            render("%DAYS%", puzzle),
+            Err(TemplateError::UnknownPlaceholder(name)) if name == "DAYS"
+        ));
+    }
+}
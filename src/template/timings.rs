@@ -1,43 +1,219 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, Error},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::machine::{MachineProfile, DEFAULT_PROFILE};
+use crate::template::stats::{MemoryStats, Stats};
+use crate::template::{data_path, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the `timings.json` schema. Files without a version only contain the flat `data` array.
+/// Timings of version 2 and older were not assigned to a machine profile.
+const TIMINGS_VERSION: u32 = 3;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Name of the machine profile the timing was measured on.
+    pub profile: String,
+    /// Time spent in the shared `parse` step, if the solution has one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Heap usage of each step, if the solutions were built with an allocator that measures it.
+    pub parse_memory: Option<MemoryStats>,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
 }
 
+/// A timing stored by a previous run of `cargo time --store`.
+#[derive(Clone, Debug)]
+pub struct TimingRecord {
+    /// Unix timestamp of the run, unknown for timings migrated from the unversioned format.
+    pub timestamp: Option<u64>,
+    /// Short hash of the benched git commit, if available.
+    pub commit: Option<String>,
+    pub timing: Timing,
+}
+
+/// The change of the mean time of a step between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingChange {
+    pub day: Day,
+    /// `"Parse"`, `"Part 1"` or `"Part 2"`.
+    pub step: &'static str,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Timing {
+    /// A timing of a day without any measured steps.
+    pub fn new(day: Day, profile: impl Into<String>) -> Self {
+        Timing {
+            day,
+            profile: profile.into(),
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_memory: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total_nanos: 0_f64,
+        }
+    }
+}
+
+impl TimingChange {
+    /// Relative change, e.g. `0.25` if the step became 25% slower.
+    pub fn relative(&self) -> f64 {
+        let before = self.before.as_secs_f64();
+        if before == 0.0 {
+            return 0.0;
+        }
+        self.after.as_secs_f64() / before - 1.0
+    }
+
+    /// Whether the step became slower by more than `threshold` (relative, e.g. `0.1` for 10%).
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.relative() > threshold
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest timing of each day.
     pub data: Vec<Timing>,
+    /// Every stored timing, oldest first.
+    pub history: Vec<TimingRecord>,
+    /// The machines the timings were measured on.
+    pub profiles: Vec<MachineProfile>,
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(data_path(year, TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    /// Fails if the file can not be read or parsed, so that the stored history is not overwritten.
+    pub fn read_from_file(year: Option<Year>) -> Result<Self, String> {
+        let path = data_path(year, TIMINGS_FILE_NAME);
+
+        match fs::read_to_string(&path) {
+            Ok(json) => {
+                Timings::try_from(json).map_err(|e| format!("failed to parse \"{path}\": {e}"))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(format!("failed to read \"{path}\": {e}")),
+        }
+    }
+
+    /// Assign all timings to a machine profile.
+    pub fn assign_profile(&mut self, profile: MachineProfile) {
+        for timing in &mut self.data {
+            timing.profile.clone_from(&profile.name);
+        }
+        self.profiles = vec![profile];
+    }
+
+    /// The timings measured on a machine profile.
+    pub fn for_profile(&self, name: &str) -> Self {
+        Timings {
+            data: self
+                .data
+                .iter()
+                .filter(|t| t.profile == name)
+                .cloned()
+                .collect(),
+            history: self
+                .history
+                .iter()
+                .filter(|r| r.timing.profile == name)
+                .cloned()
+                .collect(),
+            profiles: self
+                .profiles
+                .iter()
+                .filter(|p| p.name == name)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Names of the profiles that have timings, sorted.
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.data.iter().map(|t| t.profile.clone()).collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Append the current timings to the history.
+    pub fn record_history(&mut self, timestamp: u64, commit: Option<String>) {
+        for timing in &self.data {
+            self.history.push(TimingRecord {
+                timestamp: Some(timestamp),
+                commit: commit.clone(),
+                timing: timing.clone(),
+            });
+        }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Compare the mean times of `new` with the timings of the same days in `self`.
+    /// Steps that were not benched with stats in both runs are skipped.
+    pub fn compare(&self, new: &Self) -> Vec<TimingChange> {
+        let mut changes = vec![];
+
+        for after in &new.data {
+            let Some(before) = self
+                .data
+                .iter()
+                .find(|t| t.day == after.day && t.profile == after.profile)
+            else {
+                continue;
+            };
+
+            let steps = [
+                ("Parse", before.parse_stats, after.parse_stats),
+                ("Part 1", before.part_1_stats, after.part_1_stats),
+                ("Part 2", before.part_2_stats, after.part_2_stats),
+            ];
+
+            for (step, before, after_stats) in steps {
+                if let (Some(before), Some(after_stats)) = (before, after_stats) {
+                    changes.push(TimingChange {
+                        day: after.day,
+                        step,
+                        before: before.mean,
+                        after: after_stats.mean,
+                    });
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Merge two sets of timings, overwriting `self` with `other` if present for the same day and profile.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -46,13 +222,61 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data
+                .iter()
+                .any(|t| t.day == timing.day && t.profile == timing.profile)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_by(|a, b| (a.day, &a.profile).cmp(&(b.day, &b.profile)));
+
+        let history = self.history.iter().chain(&new.history).cloned().collect();
+
+        let mut profiles = new.profiles.clone();
+        for profile in &self.profiles {
+            if !profiles.iter().any(|p| p.name == profile.name) {
+                profiles.push(profile.clone());
+            }
+        }
+        profiles.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        Timings {
+            data,
+            history,
+            profiles,
+        }
+    }
+
+    /// Merge only the heap usage of `new` into `self`, keeping the stored times and stats.
+    /// Used for runs whose times are distorted by measuring the allocations.
+    pub fn merge_memory(&self, new: &Self) -> Self {
+        let data = new
+            .data
+            .iter()
+            .map(|timing| {
+                let stored = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day && t.profile == timing.profile);
+
+                let mut merged = stored
+                    .cloned()
+                    .unwrap_or_else(|| Timing::new(timing.day, timing.profile.clone()));
+
+                merged.parse_memory = timing.parse_memory;
+                merged.part_1_memory = timing.part_1_memory;
+                merged.part_2_memory = timing.part_2_memory;
+                merged
+            })
+            .collect();
+
+        self.merge(&Timings {
+            data,
+            history: vec![],
+            profiles: new.profiles.clone(),
+        })
     }
 
     /// Sum up total duration of timings as millis.
@@ -73,11 +297,26 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
+        map.insert(
+            "profiles".into(),
+            JsonValue::Array(value.profiles.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,19 +327,66 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match json.get("version") {
+            None => 1.0,
+            Some(v) => v
+                .get::<f64>()
+                .copied()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        if version > f64::from(TIMINGS_VERSION) {
+            return Err(format!(
+                "unsupported timings version {version}, expected {TIMINGS_VERSION} or lower."
+            ));
+        }
+
+        let data: Vec<Timing> = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
+            .ok_or("expected `json.data` to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        // NOTE: unversioned files only contain the latest timings, which seed the history.
+        let history = if version < 2.0 {
+            data.iter()
+                .map(|timing| TimingRecord {
+                    timestamp: None,
+                    commit: None,
+                    timing: timing.clone(),
+                })
+                .collect()
+        } else {
+            json.get("history")
+                .ok_or("expected JSON document to have key `history`.")?
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
+                .iter()
+                .map(TimingRecord::try_from)
+                .collect::<Result<_, _>>()?
+        };
 
-        Ok(Timings {
-            data: json_data
+        let profiles = match json.get("profiles") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.profiles` to be an array.")?
                 .iter()
-                .map(Timing::try_from)
+                .map(MachineProfile::try_from)
                 .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Timings {
+            data,
+            history,
+            profiles,
         })
     }
 }
@@ -112,8 +398,17 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +428,36 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse_stats".into(),
+            value.parse_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value.part_1_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value.part_2_stats.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "parse_memory".into(),
+            value.parse_memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_memory".into(),
+            value.part_1_memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_memory".into(),
+            value.part_2_memory.map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -151,6 +476,15 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: timings stored before profiles were tracked belong to the default profile.
+        let profile = match json.get("profile") {
+            None => DEFAULT_PROFILE.into(),
+            Some(v) => v
+                .get::<String>()
+                .ok_or("Expected timing.profile to be a string.")?
+                .clone(),
+        };
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,10 +500,47 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse times and stats are optional to stay compatible with timings stored before they were tracked.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected timing.parse to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        let parse_stats = parse_optional_stats(json.get("parse_stats"))
+            .ok_or("Expected timing.parse_stats to be null or a stats object.")?;
+
+        let part_1_stats = parse_optional_stats(json.get("part_1_stats"))
+            .ok_or("Expected timing.part_1_stats to be null or a stats object.")?;
+
+        let part_2_stats = parse_optional_stats(json.get("part_2_stats"))
+            .ok_or("Expected timing.part_2_stats to be null or a stats object.")?;
+
+        let parse_memory = parse_optional_memory(json.get("parse_memory"))
+            .ok_or("Expected timing.parse_memory to be null or a memory object.")?;
+
+        let part_1_memory = parse_optional_memory(json.get("part_1_memory"))
+            .ok_or("Expected timing.part_1_memory to be null or a memory object.")?;
+
+        let part_2_memory = parse_optional_memory(json.get("part_2_memory"))
+            .ok_or("Expected timing.part_2_memory to be null or a memory object.")?;
+
         Ok(Timing {
             day,
+            profile,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            parse_memory,
+            part_1_memory,
+            part_2_memory,
             total_nanos,
         })
     }
@@ -177,6 +548,88 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&TimingRecord> for JsonValue {
+    fn from(value: &TimingRecord) -> Self {
+        let mut json = JsonValue::from(&value.timing);
+
+        if let JsonValue::Object(map) = &mut json {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert(
+                "timestamp".into(),
+                value
+                    .timestamp
+                    .map_or(JsonValue::Null, |t| JsonValue::Number(t as f64)),
+            );
+
+            map.insert(
+                "commit".into(),
+                value
+                    .commit
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        json
+    }
+}
+
+impl TryFrom<&JsonValue> for TimingRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let timing = Timing::try_from(value)?;
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing record to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = match json.get("timestamp") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|t| *t as u64)
+                    .ok_or("Expected record.timestamp to be null or a number.")?,
+            ),
+        };
+
+        let commit = match json.get("commit") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .ok_or("Expected record.commit to be null or string.")?
+                    .clone(),
+            ),
+        };
+
+        Ok(TimingRecord {
+            timestamp,
+            commit,
+            timing,
+        })
+    }
+}
+
+fn parse_optional_stats(value: Option<&JsonValue>) -> Option<Option<Stats>> {
+    match value {
+        None => Some(None),
+        Some(v) if v.is_null() => Some(None),
+        Some(v) => Stats::try_from(v).ok().map(Some),
+    }
+}
+
+fn parse_optional_memory(value: Option<&JsonValue>) -> Option<Option<MemoryStats>> {
+    match value {
+        None => Some(None),
+        Some(v) if v.is_null() => Some(None),
+        Some(v) => MemoryStats::try_from(v).ok().map(Some),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -187,29 +640,31 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1), "default")
                 },
                 Timing {
-                    day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2), "default")
                 },
                 Timing {
-                    day: day!(4),
                     part_1: Some("40ms".into()),
-                    part_2: None,
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4), "default")
                 },
             ],
+            history: vec![],
+            profiles: vec![],
         }
     }
 
     mod deserialization {
         use crate::{day, template::timings::Timings};
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
@@ -221,6 +676,18 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert!(timing.part_1_stats.is_none());
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "2.0µs", "part_2": null, "total_nanos": 2000, "part_1_stats": { "samples": 10, "mean_nanos": 2000, "min_nanos": 1000, "median_nanos": 1500, "p95_nanos": 3000, "max_nanos": 4000, "std_dev_nanos": 100, "outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let stats = timings.data[0].part_1_stats.unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, Duration::from_nanos(1500));
+            assert_eq!(stats.outliers, 1);
+            assert!(timings.data[0].part_2_stats.is_none());
         }
 
         #[test]
@@ -230,6 +697,33 @@ mod tests {
             assert_eq!(timings.data.len(), 0);
         }
 
+        #[test]
+        fn migrates_unversioned_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 1);
+            assert_eq!(timings.history[0].timing.day, day!(1));
+            assert_eq!(timings.history[0].timestamp, None);
+            assert_eq!(timings.history[0].commit, None);
+            assert_eq!(timings.data[0].profile, "default");
+        }
+
+        #[test]
+        fn handles_json_history() {
+            let json = r#"{ "version": 2, "data": [], "history": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timestamp": 1700000000, "commit": "abc1234" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 1);
+            assert_eq!(timings.history[0].timestamp, Some(1_700_000_000));
+            assert_eq!(timings.history[0].commit, Some("abc1234".into()));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_versions() {
+            let json = r#"{ "version": 4, "data": [], "history": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         #[should_panic]
         fn panics_for_invalid_json() {
@@ -268,6 +762,79 @@ mod tests {
         }
     }
 
+    mod history {
+        use super::get_mock_timings;
+        use crate::template::timings::Timings;
+        use tinyjson::JsonValue;
+
+        #[test]
+        fn records_and_merges_history() {
+            let mut timings = get_mock_timings();
+            timings.record_history(1_700_000_000, Some("abc1234".into()));
+            assert_eq!(timings.history.len(), 3);
+
+            let mut newer = get_mock_timings();
+            newer.record_history(1_700_000_100, None);
+
+            let merged = timings.merge(&newer);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.history.len(), 6);
+            assert_eq!(merged.history[5].timestamp, Some(1_700_000_100));
+        }
+
+        #[test]
+        fn round_trips_history() {
+            let mut timings = get_mock_timings();
+            timings.record_history(1_700_000_000, Some("abc1234".into()));
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 3);
+            assert_eq!(timings.history[0].commit, Some("abc1234".into()));
+        }
+    }
+
+    mod compare {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                stats::Stats,
+                timings::{Timing, Timings},
+            },
+        };
+
+        fn timings(part_1_nanos: u64) -> Timings {
+            let stats = Stats::from_samples(&[Duration::from_nanos(part_1_nanos)]);
+            Timings {
+                data: vec![Timing {
+                    part_1: Some("1ms".into()),
+                    part_1_stats: stats,
+                    ..Timing::new(day!(1), "default")
+                }],
+                history: vec![],
+                profiles: vec![],
+            }
+        }
+
+        #[test]
+        fn flags_regressions() {
+            let changes = timings(1000).compare(&timings(1200));
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].step, "Part 1");
+            assert_eq!(changes[0].before, Duration::from_nanos(1000));
+            assert!(changes[0].is_regression(0.1));
+            assert!(!changes[0].is_regression(0.25));
+        }
+
+        #[test]
+        fn ignores_improvements_and_new_days() {
+            assert!(!timings(1000).compare(&timings(800))[0].is_regression(0.1));
+            assert!(Timings::default().compare(&timings(800)).is_empty());
+        }
+    }
+
     mod is_day_complete {
         use crate::{
             day,
@@ -278,48 +845,50 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1), "default")
                 }],
+                history: vec![],
+                profiles: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
                     part_1: Some("1ms".into()),
-                    part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1), "default")
                 }],
+                history: vec![],
+                profiles: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1), "default")],
+                history: vec![],
+                profiles: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::machine::MachineProfile,
+            template::stats::MemoryStats,
             template::timings::{Timing, Timings},
         };
 
@@ -329,12 +898,9 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3), "default")],
+                history: vec![],
+                profiles: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +915,9 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2), "default")],
+                history: vec![],
+                profiles: vec![],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +928,28 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_timings_of_other_profiles() {
+            let timings = get_mock_timings();
+            let mut other = get_mock_timings();
+            other.assign_profile(MachineProfile {
+                name: "ci".into(),
+                cpu: None,
+                cores: Some(2),
+                rustc: None,
+            });
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data.len(), 6);
+            assert_eq!(merged.profile_names(), vec!["ci", "default"]);
+            assert_eq!(merged.profiles.len(), 1);
+
+            let ci = merged.for_profile("ci");
+            assert_eq!(ci.data.len(), 3);
+            assert!(ci.data.iter().all(|t| t.profile == "ci"));
+            assert_eq!(ci.profiles[0].cores, Some(2));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -380,5 +965,34 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn merges_only_memory() {
+            let timings = get_mock_timings();
+            let memory = MemoryStats {
+                blocks: 2,
+                bytes: 64,
+                peak_bytes: 32,
+            };
+
+            let mut other = get_mock_timings();
+            other.data[0].part_1 = Some("99ms".into());
+            other.data[0].part_1_memory = Some(memory);
+            other.data[1].day = day!(3);
+            other.data[1].part_2_memory = Some(memory);
+            other.record_history(1, None);
+
+            let merged = timings.merge_memory(&other);
+            assert_eq!(merged.data.len(), 4);
+            assert!(merged.history.is_empty());
+
+            assert_eq!(merged.data[0].part_1.as_deref(), Some("10ms"));
+            assert_eq!(merged.data[0].total_nanos, 3e+10);
+            assert_eq!(merged.data[0].part_1_memory, Some(memory));
+
+            assert_eq!(merged.data[2].day, day!(3));
+            assert_eq!(merged.data[2].part_1, None);
+            assert_eq!(merged.data[2].part_2_memory, Some(memory));
+        }
     }
 }
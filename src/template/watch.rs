@@ -0,0 +1,352 @@
+//! Change detection and result summaries for `cargo watch-day`.
+//!
+//! Files are polled for their modification times, so no OS-specific notification API is needed.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use crate::template::report::{PartReport, PARSE_PART};
+use crate::template::Puzzle;
+
+/// Modification times of the files that affect a puzzle. Missing files have no time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl Snapshot {
+    /// Read the modification times of the solution, `src/lib.rs` and all example files of the puzzle.
+    pub fn take(puzzle: Puzzle) -> Self {
+        Self(
+            watched_files(puzzle)
+                .into_iter()
+                .map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                    (path, modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// Files that were created, modified or deleted since the `previous` snapshot.
+    pub fn changes<'a>(&'a self, previous: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changed: Vec<&Path> = self
+            .0
+            .iter()
+            .filter(|(path, modified)| previous.0.get(*path) != Some(modified))
+            .map(|(path, _)| path.as_path())
+            .collect();
+
+        changed.extend(
+            previous
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .map(PathBuf::as_path),
+        );
+
+        changed
+    }
+}
+
+/// The solution, `src/lib.rs` and the example files of a puzzle, e.g. `data/examples/01.txt`, `data/examples/01-2.txt` and `data/examples/01.json`.
+fn watched_files(puzzle: Puzzle) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(puzzle.bin_path()),
+        PathBuf::from("src/lib.rs"),
+    ];
+
+    let day = puzzle.day().to_string();
+    let examples = PathBuf::from(puzzle.data_path("examples", ""));
+
+    if let Ok(entries) = fs::read_dir(&examples) {
+        let mut example_files: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| is_example_file(path, &day))
+            .collect();
+        example_files.sort();
+        files.extend(example_files);
+    }
+
+    files
+}
+
+fn is_example_file(path: &Path, day: &str) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.strip_prefix(day)
+                .and_then(|rest| rest.strip_suffix(".txt").or(rest.strip_suffix(".json")))
+                // NOTE: the day must be followed by a separator, so that `01.txt` does not match `011.txt`.
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(['-', '_', '.']))
+        })
+}
+
+/// The outcome of `cargo test` for a single binary.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: usize,
+    pub failed: usize,
+    pub failures: Vec<TestFailure>,
+}
+
+/// A failed test and its panic message, e.g. the `assert_eq!` that failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TestFailure {
+    pub name: String,
+    pub message: String,
+}
+
+impl TestSummary {
+    /// Parse the output of the test harness. Returns `None` if no tests ran, e.g. because the build failed.
+    pub fn parse(stdout: &str) -> Option<Self> {
+        let mut summary: Option<Self> = None;
+        let mut messages: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut names: Vec<&str> = vec![];
+
+        let mut current: Option<&str> = None;
+        let mut in_message = false;
+
+        for line in stdout.lines() {
+            if let Some(name) = line
+                .strip_prefix("---- ")
+                .and_then(|rest| rest.strip_suffix(" stdout ----"))
+            {
+                current = Some(name);
+                in_message = false;
+            } else if let Some(name) = current {
+                // NOTE: the message ends before the backtrace or the hint on how to show it.
+                if (line.is_empty() && in_message)
+                    || line.starts_with("stack backtrace:")
+                    || line.starts_with("note: ")
+                    || line == "failures:"
+                {
+                    current = None;
+                } else if in_message || line.contains("panicked at") {
+                    in_message = true;
+                    messages.entry(name).or_default().push(line);
+                }
+            } else if let Some(name) = line.strip_prefix("    ") {
+                names.push(name.trim());
+            } else if let Some(result) = line.strip_prefix("test result: ") {
+                let count = |label: &str| {
+                    result
+                        .split([';', '.'])
+                        .filter_map(|part| part.trim().strip_suffix(label))
+                        .find_map(|count| count.trim().parse::<usize>().ok())
+                        .unwrap_or(0)
+                };
+
+                let total = summary.get_or_insert_with(Self::default);
+                total.passed += count(" passed");
+                total.failed += count(" failed");
+            }
+        }
+
+        summary.map(|summary| Self {
+            failures: names
+                .into_iter()
+                .map(|name| TestFailure {
+                    name: name.to_string(),
+                    message: messages.get(name).map(|m| m.join("\n")).unwrap_or_default(),
+                })
+                .collect(),
+            ..summary
+        })
+    }
+}
+
+impl std::fmt::Display for TestSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.failed == 0 {
+            write!(f, "✔ {} passed", self.passed)
+        } else {
+            write!(f, "✖ {} failed, {} passed", self.failed, self.passed)?;
+            if !self.failures.is_empty() {
+                let names: Vec<&str> = self.failures.iter().map(|f| f.name.as_str()).collect();
+                write!(f, " ({})", names.join(", "))?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// One line per part, comparing the answers of the `current` run with those of the `previous` run.
+pub fn diff_answers(previous: Option<&[PartReport]>, current: &[PartReport]) -> Vec<String> {
+    let format_answer = |answer: Option<&str>| match answer {
+        Some(answer) if answer.contains('\n') => format!("▼\n{answer}"),
+        Some(answer) => answer.to_string(),
+        None => "✖".into(),
+    };
+
+    current
+        .iter()
+        .filter(|report| report.part != PARSE_PART)
+        .map(|report| {
+            let answer = report.answer.as_deref();
+            let line = format!(
+                "Part {}: {} ({:.1?})",
+                report.part,
+                format_answer(answer),
+                report.stats.mean
+            );
+
+            let Some(previous) = previous else {
+                return line;
+            };
+
+            match previous.iter().find(|p| p.part == report.part) {
+                Some(p) if p.answer.as_deref() == answer => format!("{line} · unchanged"),
+                Some(p) => format!("{line} · was {}", format_answer(p.answer.as_deref())),
+                None => format!("{line} · new"),
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff_answers, is_example_file, Snapshot, TestFailure, TestSummary};
+    use crate::template::report::mock_report;
+    use std::{
+        path::{Path, PathBuf},
+        time::{Duration, SystemTime},
+    };
+
+    #[test]
+    fn matches_example_files() {
+        assert!(is_example_file(Path::new("data/examples/01.txt"), "01"));
+        assert!(is_example_file(Path::new("data/examples/01-2.txt"), "01"));
+        assert!(is_example_file(Path::new("data/examples/01.json"), "01"));
+        assert!(!is_example_file(Path::new("data/examples/011.txt"), "01"));
+        assert!(!is_example_file(Path::new("data/examples/02.txt"), "01"));
+        assert!(!is_example_file(Path::new("data/examples/01.md"), "01"));
+    }
+
+    #[test]
+    fn detects_changes() {
+        let at = |secs: u64| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let snapshot = |files: &[(&str, Option<SystemTime>)]| {
+            Snapshot(
+                files
+                    .iter()
+                    .map(|(path, modified)| (PathBuf::from(path), *modified))
+                    .collect(),
+            )
+        };
+
+        let previous = snapshot(&[("a", at(1)), ("b", at(1)), ("c", None), ("d", at(1))]);
+        let current = snapshot(&[("a", at(1)), ("b", at(2)), ("c", at(2))]);
+
+        assert_eq!(
+            current.changes(&previous),
+            vec![Path::new("b"), Path::new("c"), Path::new("d")]
+        );
+        assert!(current.changes(&current).is_empty());
+    }
+
+    #[test]
+    fn parses_test_output() {
+        let stdout = "
+running 3 tests
+..F
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' (42) panicked at src/bin/01.rs:68:9:
+assertion `left == right` failed
+  left: Some(11)
+ right: Some(12)
+stack backtrace:
+   0: __rustc::rust_begin_unwind
+
+---- tests::test_parse stdout ----
+
+thread 'tests::test_parse' (43) panicked at src/bin/01.rs:75:9:
+explicit panic
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::test_part_two
+    tests::test_parse
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+        let summary = TestSummary::parse(stdout).unwrap();
+        assert_eq!(
+            summary,
+            TestSummary {
+                passed: 1,
+                failed: 2,
+                failures: vec![
+                    TestFailure {
+                        name: "tests::test_part_two".into(),
+                        message: "thread 'tests::test_part_two' (42) panicked at src/bin/01.rs:68:9:\nassertion `left == right` failed\n  left: Some(11)\n right: Some(12)".into(),
+                    },
+                    TestFailure {
+                        name: "tests::test_parse".into(),
+                        message: "thread 'tests::test_parse' (43) panicked at src/bin/01.rs:75:9:\nexplicit panic".into(),
+                    },
+                ],
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "✖ 2 failed, 1 passed (tests::test_part_two, tests::test_parse)"
+        );
+
+        let passing =
+            TestSummary::parse("test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured")
+                .unwrap();
+        assert_eq!(passing.to_string(), "✔ 2 passed");
+
+        assert!(TestSummary::parse("error[E0425]: cannot find value `x`").is_none());
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let previous = [
+            mock_report(0, None, &[2_000], false),
+            mock_report(1, Some("11"), &[2_000], false),
+            mock_report(2, Some("30"), &[2_000], false),
+        ];
+
+        assert_eq!(
+            diff_answers(None, &previous),
+            vec!["Part 1: 11 (2.0µs)", "Part 2: 30 (2.0µs)"]
+        );
+
+        assert_eq!(
+            diff_answers(
+                Some(&previous),
+                &[
+                    mock_report(1, Some("11"), &[2_000], false),
+                    mock_report(2, Some("31"), &[2_000], false)
+                ]
+            ),
+            vec![
+                "Part 1: 11 (2.0µs) · unchanged",
+                "Part 2: 31 (2.0µs) · was 30"
+            ]
+        );
+
+        assert_eq!(
+            diff_answers(
+                Some(&[mock_report(1, Some("11"), &[2_000], false)]),
+                &[
+                    mock_report(1, None, &[2_000], false),
+                    mock_report(2, Some("1"), &[2_000], false)
+                ]
+            ),
+            vec!["Part 1: ✖ (2.0µs) · was 11", "Part 2: 1 (2.0µs) · new"]
+        );
+    }
+}